@@ -0,0 +1,340 @@
+//! Advisory checks, which produce warnings but don't fail the package.
+
+use cargo_metadata::PackageId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::policy;
+
+/// A problem found by an advisory check, which doesn't cause the check to fail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Warning {
+    /// What the warning is about.
+    pub scope: WarningScope,
+    /// A short, stable identifier for the kind of warning, such as `priority-override`.
+    pub code: String,
+    /// A description of the problem.
+    pub message: String,
+}
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// What a [`Warning`] is about.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningScope {
+    /// A single package in the workspace.
+    Package(PackageId),
+    /// The workspace as a whole, such as the lints in its root manifest.
+    Workspace,
+}
+
+/// Find lints in a member's own `[lints]` tables which set a `priority`.
+///
+/// A member-level `priority` can reorder lints relative to each other in ways the workspace's
+/// lint policy didn't intend, so these are worth flagging even though they aren't errors.
+pub(crate) fn priority_overrides(manifest: &toml::Table) -> Vec<String> {
+    let Some(lints) = manifest.get("lints").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    lints
+        .iter()
+        .filter_map(|(tool, lints)| Some((tool, lints.as_table()?)))
+        .flat_map(|(tool, lints)| {
+            lints.iter().filter_map(move |(lint, config)| {
+                let priority = config.get("priority")?;
+                Some(format!(
+                    "`lints.{tool}.{lint}` sets `priority = {priority}`, which may override the \
+                     workspace lint policy"
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Lints which cover so much that allowing them defeats the point of inheriting the workspace's
+/// lints, keyed by tool.
+const BROAD_LINTS: &[(&str, &[&str])] = &[
+    ("rust", &["warnings"]),
+    (
+        "clippy",
+        &[
+            "all",
+            "cargo",
+            "complexity",
+            "correctness",
+            "nursery",
+            "pedantic",
+            "perf",
+            "style",
+            "suspicious",
+        ],
+    ),
+];
+
+/// Find lints which a member inheriting the workspace's lints allows wholesale anyway.
+///
+/// For example, `[lints.rust] warnings = "allow"` next to `workspace = true` neuters any lints
+/// the workspace denies. Cargo itself rejects most such manifests, but they can still turn up in
+/// manifests checked without a workspace.
+pub(crate) fn negating_overrides(manifest: &toml::Table) -> Vec<String> {
+    let Some(lints) = manifest.get("lints").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    if lints.get("workspace") != Some(&toml::Value::Boolean(true)) {
+        return Vec::new();
+    }
+    BROAD_LINTS
+        .iter()
+        .filter_map(|(tool, broad_lints)| Some((tool, broad_lints, lints.get(*tool)?.as_table()?)))
+        .flat_map(|(tool, broad_lints, lints)| {
+            broad_lints.iter().filter_map(move |lint| {
+                let config = lints.get(*lint)?;
+                let level = config.as_str().or_else(|| config.get("level")?.as_str())?;
+                (level == "allow").then(|| {
+                    format!(
+                        "`lints.{tool}.{lint}` is set to `allow`, which defeats the inherited \
+                         workspace lints"
+                    )
+                })
+            })
+        })
+        .collect()
+}
+
+/// Which source files to scan for crate-level `#![allow(...)]` and `#![expect(...)]` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceFiles {
+    /// Only the root file of each library and binary target, such as `src/lib.rs`.
+    CrateRoots,
+    /// Every `.rs` file under the directories containing those root files.
+    All,
+}
+
+/// Find lints configured in the root `[workspace.lints]` which inner `#![allow(...)]` or
+/// `#![expect(...)]` attributes in `source` turn off anyway.
+///
+/// Attributes may span several lines, and those nested in `#![cfg_attr(..., allow(...))]` are
+/// found whatever the condition. Comments and string literals are passed over, so commented-out
+/// attributes aren't reported. Allowing one of the broad lints (such as `warnings` or
+/// `clippy::pedantic`) is reported even if the workspace doesn't configure it, since it overrides
+/// everything the group covers.
+pub(crate) fn source_allows(source: &str, workspace_lints: &toml::Table) -> Vec<String> {
+    let tokens = tokenize(source);
+    let mut allowed = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i..].starts_with(&[Token::Punct('#'), Token::Punct('!'), Token::Punct('[')]) {
+            i += 3;
+            let attribute = token_trees(&tokens, &mut i);
+            meta_allows(&attribute, &mut allowed);
+        } else {
+            i += 1;
+        }
+    }
+    let mut found = Vec::new();
+    for lint in allowed {
+        let (tool, name) = policy::split_lint(&lint);
+        let configured = workspace_lints
+            .get(tool)
+            .and_then(toml::Value::as_table)
+            .is_some_and(|lints| lints.contains_key(name));
+        let broad = BROAD_LINTS
+            .iter()
+            .any(|(broad_tool, lints)| *broad_tool == tool && lints.contains(&name));
+        if (configured || broad) && !found.contains(&lint) {
+            found.push(lint);
+        }
+    }
+    found
+}
+
+/// A token of Rust source, as far as is needed to read attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    /// An identifier or keyword.
+    Ident(&'a str),
+    /// A string, character or number literal.
+    Literal,
+    /// Any other character, such as `#` or `(`.
+    Punct(char),
+}
+
+/// Split `source` into tokens, leaving out whitespace and comments.
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest[0].is_ascii_whitespace() {
+            i += 1;
+        } else if rest.starts_with(b"//") {
+            i += rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        } else if rest.starts_with(b"/*") {
+            i += block_comment_len(rest);
+        } else if let Some(len) =
+            string_literal_len(rest).or_else(|| char_literal_len(&source[i..]))
+        {
+            tokens.push(Token::Literal);
+            i += len;
+        } else if rest[0].is_ascii_alphanumeric() || rest[0] == b'_' || !rest[0].is_ascii() {
+            let len = rest
+                .iter()
+                .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii()))
+                .unwrap_or(rest.len());
+            tokens.push(if rest[0].is_ascii_digit() {
+                Token::Literal
+            } else {
+                Token::Ident(&source[i..i + len])
+            });
+            i += len;
+        } else {
+            tokens.push(Token::Punct(char::from(rest[0])));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// The length of the (possibly nested) block comment at the start of `rest`.
+fn block_comment_len(rest: &[u8]) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < rest.len() {
+        if rest[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if rest[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    rest.len()
+}
+
+/// The length of the string literal at the start of `rest`, if there is one.
+///
+/// This covers plain, byte, C and raw strings, along with byte character literals.
+fn string_literal_len(rest: &[u8]) -> Option<usize> {
+    let prefix = rest
+        .iter()
+        .position(|&b| !matches!(b, b'b' | b'c' | b'r'))
+        .filter(|&prefix| prefix <= 2)?;
+    if rest[..prefix].contains(&b'r') {
+        let hashes = rest[prefix..].iter().take_while(|&&b| b == b'#').count();
+        let start = prefix + hashes;
+        if rest.get(start) != Some(&b'"') {
+            return None;
+        }
+        let mut terminator = vec![b'"'];
+        terminator.resize(hashes + 1, b'#');
+        return Some(
+            rest[start + 1..]
+                .windows(terminator.len())
+                .position(|window| window == terminator)
+                .map_or(rest.len(), |end| start + 1 + end + terminator.len()),
+        );
+    }
+    let quote = *rest.get(prefix)?;
+    if quote != b'"' && !(quote == b'\'' && rest[..prefix] == *b"b") {
+        return None;
+    }
+    let mut i = prefix + 1;
+    while i < rest.len() {
+        match rest[i] {
+            b'\\' => i += 2,
+            b if b == quote => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    Some(rest.len())
+}
+
+/// The length of the character literal at the start of `rest`, if there is one.
+///
+/// A `'` which starts a lifetime or label isn't a literal, and is left to be read as punctuation
+/// followed by an identifier.
+fn char_literal_len(rest: &str) -> Option<usize> {
+    if !rest.starts_with('\'') {
+        return None;
+    }
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next()? {
+        (_, '\\') => rest.get(3..)?.find('\'').map(|end| end + 4),
+        (_, _) => chars
+            .next()
+            .filter(|(_, c)| *c == '\'')
+            .map(|(end, _)| end + 1),
+    }
+}
+
+/// A token, or a bracketed group of tokens, within an attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenTree {
+    /// A path, such as `allow` or `clippy::pedantic`.
+    Path(String),
+    /// The contents of a pair of brackets.
+    Group(Vec<TokenTree>),
+    /// Any other token.
+    Other(char),
+}
+
+/// Read the token trees from `tokens[*i..]` up to the bracket which closes the group, advancing
+/// `i` past it.
+fn token_trees(tokens: &[Token<'_>], i: &mut usize) -> Vec<TokenTree> {
+    let mut trees = Vec::new();
+    while let Some(&token) = tokens.get(*i) {
+        *i += 1;
+        match token {
+            Token::Punct('(' | '[' | '{') => trees.push(TokenTree::Group(token_trees(tokens, i))),
+            Token::Punct(')' | ']' | '}') => break,
+            Token::Ident(ident) => {
+                let mut path = ident.to_owned();
+                while let [Token::Punct(':'), Token::Punct(':'), Token::Ident(segment), ..] =
+                    tokens[*i..]
+                {
+                    path.push_str("::");
+                    path.push_str(segment);
+                    *i += 3;
+                }
+                trees.push(TokenTree::Path(path));
+            }
+            Token::Punct(punct) => trees.push(TokenTree::Other(punct)),
+            Token::Literal => trees.push(TokenTree::Other('"')),
+        }
+    }
+    trees
+}
+
+/// Collect the lints which the attribute `meta` allows or expects into `allowed`, looking inside
+/// `cfg_attr`.
+fn meta_allows(meta: &[TokenTree], allowed: &mut Vec<String>) {
+    let [TokenTree::Path(name), TokenTree::Group(arguments)] = meta else {
+        return;
+    };
+    let arguments = arguments.split(|tree| *tree == TokenTree::Other(','));
+    match name.as_str() {
+        "allow" | "expect" => {
+            // Anything other than a lone path, such as `reason = "..."`, isn't a lint.
+            allowed.extend(arguments.filter_map(|argument| match argument {
+                [TokenTree::Path(lint)] => Some(lint.clone()),
+                _ => None,
+            }));
+        }
+        // The first argument is the condition, and the rest are the attributes it applies.
+        "cfg_attr" => arguments
+            .skip(1)
+            .for_each(|meta| meta_allows(meta, allowed)),
+        _ => {}
+    }
+}
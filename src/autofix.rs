@@ -0,0 +1,178 @@
+//! Planning (and applying) fixes for failing packages.
+
+use cargo_metadata::{camino::Utf8PathBuf, PackageId};
+use serde::Serialize;
+use std::{fs, io};
+
+use crate::{PackageResult, PackageStatus, PackageValidationErrorKind};
+
+/// An edit which would make a failing package pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedFix {
+    /// The package which would be fixed.
+    pub package: PackageId,
+    /// The manifest which would be edited.
+    pub manifest_path: Utf8PathBuf,
+    /// The edit to make to the manifest.
+    pub edit: FixEdit,
+}
+
+/// The kinds of edits we can make to a manifest.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FixEdit {
+    /// Add `workspace = true` to the `[lints]` table, creating the table if it doesn't exist.
+    InsertWorkspaceKey,
+    /// Replace the existing value of `lints.workspace` with `true`.
+    ReplaceWorkspaceValue {
+        /// The value which would be replaced.
+        previous: toml::Value,
+    },
+    /// Remove the existing `lints.workspace` key, and the `[lints]` table if it is left empty.
+    RemoveWorkspaceKey {
+        /// The value which would be removed.
+        previous: toml::Value,
+    },
+}
+
+impl FixEdit {
+    /// Whether the package passes the check once the edit is made.
+    ///
+    /// This is true of every edit except [`FixEdit::RemoveWorkspaceKey`], which leaves the key
+    /// missing.
+    #[must_use]
+    pub fn makes_package_pass(&self) -> bool {
+        !matches!(self, Self::RemoveWorkspaceKey { .. })
+    }
+}
+
+/// How to fix packages which explicitly set `lints.workspace = false`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FalseFix {
+    /// Remove the key, so that it is unambiguously not set.
+    ///
+    /// The package still fails the check afterwards, since the key is then missing.
+    Remove,
+    /// Set the key to `true`.
+    #[default]
+    SetTrue,
+}
+
+/// Plan the edits needed to fix every failing package in the results.
+///
+/// Packages which pass, or which fail in a way we can't automatically fix, are left out of the
+/// plan. Packages with `lints.workspace = false` are fixed according to `false_fix`.
+#[must_use]
+pub fn plan_fixes<'a>(
+    results: impl IntoIterator<Item = &'a PackageResult>,
+    false_fix: FalseFix,
+) -> Vec<PlannedFix> {
+    results
+        .into_iter()
+        .filter_map(|result| {
+            let PackageStatus::Failed(kind) = &result.status else {
+                return None;
+            };
+            let edit = match kind {
+                PackageValidationErrorKind::WorkspaceLintsMissing => FixEdit::InsertWorkspaceKey,
+                PackageValidationErrorKind::WorkspaceLintsWrongValue(
+                    previous @ toml::Value::Boolean(false),
+                ) if false_fix == FalseFix::Remove => FixEdit::RemoveWorkspaceKey {
+                    previous: previous.clone(),
+                },
+                PackageValidationErrorKind::WorkspaceLintsWrongValue(previous) => {
+                    FixEdit::ReplaceWorkspaceValue {
+                        previous: previous.clone(),
+                    }
+                }
+                PackageValidationErrorKind::InlineLints(_)
+                | PackageValidationErrorKind::PackageLintsMissing
+                | PackageValidationErrorKind::HookFailed { .. }
+                | PackageValidationErrorKind::DuplicateLintsTable { .. }
+                | PackageValidationErrorKind::LocalLintsPresent(_)
+                | PackageValidationErrorKind::RequiredKeyMissing(_)
+                | PackageValidationErrorKind::RequiredKeyWrongValue { .. }
+                | PackageValidationErrorKind::FieldNotInherited(_)
+                | PackageValidationErrorKind::CheckFailed { .. }
+                | PackageValidationErrorKind::ManifestReadError(_)
+                | PackageValidationErrorKind::ManifestParseError(_) => return None,
+            };
+            Some(PlannedFix {
+                package: result.package.clone(),
+                manifest_path: result.manifest_path.clone(),
+                edit,
+            })
+        })
+        .collect()
+}
+
+/// Make the planned edit to the package's manifest.
+///
+/// The manifest is edited as by [`fixed_manifest`].
+///
+/// # Errors
+/// Returns an error if the manifest couldn't be read, parsed, or written.
+pub fn apply_fix(fix: &PlannedFix) -> io::Result<()> {
+    fs::write(&fix.manifest_path, fixed_manifest(fix)?)
+}
+
+/// The contents the package's manifest would have after making the planned edit, without
+/// writing them.
+///
+/// The manifest is edited with `toml_edit`, so comments and formatting elsewhere in the file are
+/// preserved. A newly-created `[lints]` table is added at the end of the file. Packages which
+/// define their own lints can't be fixed this way, since Cargo doesn't allow setting
+/// `workspace = true` alongside them.
+///
+/// # Errors
+/// Returns an error if the manifest couldn't be read or parsed.
+pub fn fixed_manifest(fix: &PlannedFix) -> io::Result<String> {
+    let mut manifest = fs::read_to_string(&fix.manifest_path)?
+        .parse::<toml_edit::Document>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    match fix.edit {
+        FixEdit::InsertWorkspaceKey | FixEdit::ReplaceWorkspaceValue { .. } => {
+            let lints = manifest
+                .entry("lints")
+                .or_insert_with(toml_edit::table)
+                .as_table_like_mut()
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "`lints` is not a table")
+                })?;
+            // Cargo rejects `workspace = true` alongside the package's own lints, so those need
+            // merging into the workspace by hand.
+            if lints.iter().any(|(key, _)| key != "workspace") {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "`[lints]` has its own lint tables, which must be removed first",
+                ));
+            }
+            match lints
+                .get_mut("workspace")
+                .and_then(toml_edit::Item::as_value_mut)
+            {
+                // Keep any comments around the old value.
+                Some(value) => {
+                    let decor = value.decor().clone();
+                    *value = true.into();
+                    *value.decor_mut() = decor;
+                }
+                None => {
+                    lints.insert("workspace", toml_edit::value(true));
+                }
+            }
+        }
+        FixEdit::RemoveWorkspaceKey { .. } => {
+            if let Some(lints) = manifest
+                .get_mut("lints")
+                .and_then(toml_edit::Item::as_table_like_mut)
+            {
+                lints.remove("workspace");
+                if lints.is_empty() {
+                    manifest.remove("lints");
+                }
+            }
+        }
+    }
+    Ok(manifest.to_string())
+}
@@ -0,0 +1,94 @@
+//! Recording known failures, so that only new ones fail the check.
+
+use cargo_metadata::camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use std::{fs, io};
+
+use crate::{json::FailureKind, PackageStatus, SkipReason, WorkspaceReport};
+
+/// The packages which were failing when the baseline was written, and why.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The known failures.
+    pub failures: Vec<KnownFailure>,
+}
+
+/// A known failure in a [`Baseline`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnownFailure {
+    /// The name of the failing package.
+    ///
+    /// Packages are identified by name rather than [`cargo_metadata::PackageId`], so that the
+    /// baseline still applies after their versions change.
+    pub name: String,
+    /// Why the package was failing.
+    #[serde(flatten)]
+    pub kind: FailureKind,
+}
+
+impl Baseline {
+    /// Record every failing package in the report.
+    #[must_use]
+    pub fn new(report: &WorkspaceReport) -> Self {
+        let mut failures = Vec::<KnownFailure>::new();
+        for result in &report.packages {
+            let result = &result.result;
+            if let PackageStatus::Failed(kind) = &result.status {
+                let failure = KnownFailure {
+                    name: result.name.clone(),
+                    kind: FailureKind::new(kind),
+                };
+                // The same package may be surfaced under several configurations.
+                if !failures.contains(&failure) {
+                    failures.push(failure);
+                }
+            }
+        }
+        Self { failures }
+    }
+
+    /// Read a baseline written by [`Baseline::write`].
+    ///
+    /// # Errors
+    /// Returns an error if the file couldn't be read or isn't a valid baseline.
+    pub fn load(path: &Utf8Path) -> io::Result<Self> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write the baseline to `path`, as JSON.
+    ///
+    /// # Errors
+    /// Returns an error if the file couldn't be written.
+    ///
+    /// # Panics
+    /// Never, since baselines always serialize to JSON.
+    pub fn write(&self, path: &Utf8Path) -> io::Result<()> {
+        let mut contents = serde_json::to_string_pretty(self).expect("Baselines serialize to JSON");
+        contents.push('\n');
+        fs::write(path, contents)
+    }
+
+    /// Mark the failing packages in the report which fail the same way as in the baseline as
+    /// skipped, with [`SkipReason::Baselined`], and return how many there were.
+    ///
+    /// Packages which have started failing, or fail for a different reason than recorded, are
+    /// left failing.
+    pub fn apply(&self, report: &mut WorkspaceReport) -> usize {
+        let mut suppressed = 0;
+        for result in &mut report.packages {
+            let result = &mut result.result;
+            let PackageStatus::Failed(kind) = &result.status else {
+                continue;
+            };
+            let known = self.failures.iter().any(|failure| {
+                failure.name == result.name && failure.kind == FailureKind::new(kind)
+            });
+            if known {
+                result.status = PackageStatus::Skipped(SkipReason::Baselined);
+                suppressed += 1;
+            }
+        }
+        suppressed
+    }
+}
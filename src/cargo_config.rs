@@ -0,0 +1,242 @@
+//! Checking the `rustflags` in cargo config files, which take precedence over `[workspace.lints]`.
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+};
+
+use crate::{
+    path::relative_path,
+    policy::{self, LintLevel},
+    WorkspaceValidationError,
+};
+
+/// A flag in `rustflags` which affects the levels of lints.
+enum LintFlag {
+    /// The lint (or group), as in source code with `-` replaced by `_`, is set to the level, as
+    /// with `-A` or `--deny`.
+    Level(String, LintLevel),
+    /// Every lint is capped at the level, with `--cap-lints`.
+    Cap(LintLevel),
+}
+
+/// Find the flags in the `rustflags` of the cargo config files for the workspace which change
+/// the levels of lints set in the root `[workspace.lints]`.
+///
+/// Cargo passes `rustflags` to the compiler after the flags for `[workspace.lints]`, so a flag
+/// such as `-A clippy::all` in `build.rustflags` or `target.<triple>.rustflags` silently
+/// overrides them. The config files are the `.cargo/config.toml` (or legacy `.cargo/config`) in
+/// the workspace root and each of its parent directories, as cargo reads when run there. The
+/// `RUSTFLAGS` environment variable isn't considered. The lints in each group set by a flag are
+/// listed by running `rustc`, `clippy-driver`, or `rustdoc` with `-W help`, as needed.
+///
+/// # Errors
+/// Returns an error if a config file couldn't be read and parsed, or one of the programs couldn't
+/// be run.
+pub(crate) fn rustflag_conflicts(
+    workspace_root: &Utf8Path,
+    workspace_lints: &toml::Table,
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    // The level of each lint in the root, keyed by its name as in source code.
+    let root_levels = workspace_lints
+        .iter()
+        .filter_map(|(tool, lints)| Some((tool, lints.as_table()?)))
+        .flat_map(|(tool, lints)| {
+            lints.iter().filter_map(move |(lint, config)| {
+                let lint = if tool == "rust" {
+                    lint.replace('-', "_")
+                } else {
+                    format!("{tool}::{}", lint.replace('-', "_"))
+                };
+                Some((lint, policy::lint_level(config)?))
+            })
+        })
+        .collect::<HashMap<_, _>>();
+    let mut groups = HashMap::new();
+    let mut problems = Vec::new();
+    for path in config_files(workspace_root) {
+        let Some(config) = read_config(&path)? else {
+            continue;
+        };
+        let file = relative_path(&path, workspace_root);
+        for (key, flags) in rustflags(&config) {
+            for flag in lint_flags(&flags) {
+                let (setting, overridden) = match flag {
+                    LintFlag::Level(lint, level) => {
+                        let overridden = match root_levels.get(&lint) {
+                            Some(&root_level) if root_level != level => vec![lint.clone()],
+                            Some(_) => Vec::new(),
+                            None => group_members(&lint, &root_levels, &mut groups)?
+                                .into_iter()
+                                .filter(|member| root_levels[member] != level)
+                                .collect(),
+                        };
+                        (format!("sets `{lint}` to `{level}`"), overridden)
+                    }
+                    LintFlag::Cap(level) => (
+                        format!("caps lints at `{level}`"),
+                        root_levels
+                            .iter()
+                            .filter(|(_, &root_level)| root_level > level)
+                            .map(|(lint, _)| lint.clone())
+                            .collect(),
+                    ),
+                };
+                if overridden.is_empty() {
+                    continue;
+                }
+                let mut overridden = overridden
+                    .iter()
+                    .map(|lint| format!("`{lint}`"))
+                    .collect::<Vec<_>>();
+                overridden.sort();
+                problems.push(format!(
+                    "`{key}` in `{file}` {setting}, overriding `[workspace.lints]` for {}",
+                    overridden.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// The cargo config files which apply in the workspace root, from the most specific.
+fn config_files(workspace_root: &Utf8Path) -> Vec<Utf8PathBuf> {
+    workspace_root
+        .ancestors()
+        .filter_map(|dir| {
+            let dir = dir.join(".cargo");
+            // Cargo prefers `config` if both exist, for compatibility.
+            [dir.join("config"), dir.join("config.toml")]
+                .into_iter()
+                .find(|path| path.is_file())
+        })
+        .collect()
+}
+
+/// Read and parse a cargo config file, returning `None` if it doesn't exist.
+fn read_config(path: &Utf8Path) -> Result<Option<toml::Table>, WorkspaceValidationError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(WorkspaceValidationError::Io {
+                path: path.to_owned(),
+                error,
+            })
+        }
+    };
+    toml::from_str(&contents)
+        .map(Some)
+        .map_err(|error| WorkspaceValidationError::Toml {
+            path: path.to_owned(),
+            error,
+        })
+}
+
+/// The `rustflags` set in a cargo config, with the key each is set with, such as
+/// `build.rustflags`.
+///
+/// The flags may be given as a single string, split on whitespace, or as an array of strings.
+fn rustflags(config: &toml::Table) -> Vec<(String, Vec<String>)> {
+    let build = config
+        .get("build")
+        .and_then(|build| build.get("rustflags"))
+        .map(|flags| ("build.rustflags".to_owned(), flags));
+    let targets = config
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten()
+        .filter_map(|(target, config)| {
+            Some((
+                format!("target.{target}.rustflags"),
+                config.get("rustflags")?,
+            ))
+        });
+    build
+        .into_iter()
+        .chain(targets)
+        .map(|(key, flags)| {
+            let flags = match flags {
+                toml::Value::String(flags) => flags.split_whitespace().map(str::to_owned).collect(),
+                toml::Value::Array(flags) => flags
+                    .iter()
+                    .filter_map(|flag| Some(flag.as_str()?.to_owned()))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            (key, flags)
+        })
+        .collect()
+}
+
+/// Find the flags which affect lint levels, in any of the forms `rustc` accepts, such as `-A lint`,
+/// `-Alint`, `--allow lint`, or `--allow=lint`.
+fn lint_flags(flags: &[String]) -> Vec<LintFlag> {
+    let mut found = Vec::new();
+    let mut flags = flags.iter().map(String::as_str);
+    while let Some(flag) = flags.next() {
+        let (name, value) = if let Some(long) = flag.strip_prefix("--") {
+            match long.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (long, None),
+            }
+        } else if let Some(short) = flag.strip_prefix('-').filter(|short| !short.is_empty()) {
+            let (name, value) = short.split_at(short.chars().next().map_or(0, char::len_utf8));
+            (name, Some(value).filter(|value| !value.is_empty()))
+        } else {
+            continue;
+        };
+        let level = match name {
+            "A" | "allow" => LintLevel::Allow,
+            "W" | "warn" | "force-warn" => LintLevel::Warn,
+            "D" | "deny" => LintLevel::Deny,
+            "F" | "forbid" => LintLevel::Forbid,
+            "cap-lints" => {
+                if let Some(level) = value.or_else(|| flags.next()).and_then(LintLevel::parse) {
+                    found.push(LintFlag::Cap(level));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(lint) = value.or_else(|| flags.next()) {
+            found.push(LintFlag::Level(lint.replace('-', "_"), level));
+        }
+    }
+    found
+}
+
+/// The lints set in the root which are in the group `lint`, or nothing if it isn't a group.
+///
+/// `warnings` counts as the group of every lint set to `warn`. The groups of each tool are listed
+/// once, and kept in `groups`.
+fn group_members(
+    lint: &str,
+    root_levels: &HashMap<String, LintLevel>,
+    groups: &mut HashMap<&'static str, HashMap<String, HashSet<String>>>,
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    if lint == "warnings" {
+        return Ok(root_levels
+            .iter()
+            .filter(|(_, &level)| level == LintLevel::Warn)
+            .map(|(lint, _)| lint.clone())
+            .collect());
+    }
+    let (tool, _) = policy::split_lint(lint);
+    let Some(&(_, program)) = policy::LINT_TOOLS.iter().find(|(known, _)| *known == tool) else {
+        return Ok(Vec::new());
+    };
+    if !groups.contains_key(program) {
+        groups.insert(program, policy::lint_groups(program)?);
+    }
+    Ok(groups[program]
+        .get(lint)
+        .into_iter()
+        .flatten()
+        .filter(|member| root_levels.contains_key(*member))
+        .cloned()
+        .collect())
+}
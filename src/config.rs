@@ -0,0 +1,271 @@
+//! Reading default options from a config file beside the workspace root manifest, or from the
+//! root manifest itself.
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, io};
+
+use crate::{
+    ExcludePattern, LintLevel, LocalLintPattern, NamedCheck, RequiredLint, Severity, SourceFiles,
+    ValidationOptions,
+};
+
+/// The name of the config file, which is looked for in the workspace root directory.
+pub const CONFIG_FILE_NAME: &str = ".cargo-workspace-lints.toml";
+
+/// Defaults for the options used to check a workspace, read from [`CONFIG_FILE_NAME`] or from
+/// `workspace.metadata.workspace-lints` in the root manifest.
+///
+/// The boolean options are `None` where the config doesn't mention them. They can only turn
+/// options on, as [`Config::apply_defaults`] describes.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Dotted paths to keys which must be `true`, as in [`ValidationOptions::required_keys`].
+    #[serde(default)]
+    pub require: Vec<String>,
+    /// Fields of `[package]` which must be inherited, as in
+    /// [`ValidationOptions::inherited_fields`].
+    #[serde(default)]
+    pub inherit: Vec<String>,
+    /// Patterns for packages to skip, as parsed by [`ExcludePattern::parse`].
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Whether to check unpublished packages, as in [`ValidationOptions::include_unpublished`].
+    #[serde(default)]
+    pub include_unpublished: Option<bool>,
+    /// As in [`ValidationOptions::detect_inline_lints`].
+    #[serde(default)]
+    pub detect_inline_lints: Option<bool>,
+    /// As in [`ValidationOptions::check_priority_overrides`].
+    #[serde(default)]
+    pub check_priority_overrides: Option<bool>,
+    /// As in [`ValidationOptions::check_negating_overrides`].
+    #[serde(default)]
+    pub check_negating_overrides: Option<bool>,
+    /// As in [`ValidationOptions::check_root_lints_sorted`].
+    #[serde(default)]
+    pub check_root_lints_sorted: Option<bool>,
+    /// As in [`ValidationOptions::check_root_lint_priorities`].
+    #[serde(default)]
+    pub check_lint_priorities: Option<bool>,
+    /// As in [`ValidationOptions::check_root_lint_consistency`].
+    #[serde(default)]
+    pub check_lint_consistency: Option<bool>,
+    /// As in [`ValidationOptions::check_rustflags`].
+    #[serde(default)]
+    pub check_rustflags: Option<bool>,
+    /// As in [`ValidationOptions::check_escaped_packages`].
+    #[serde(default)]
+    pub check_escaped_packages: Option<bool>,
+    /// As in [`ValidationOptions::reject_local_lints`].
+    #[serde(default)]
+    pub no_local_lints: Option<bool>,
+    /// As in [`ValidationOptions::allow_single_package`].
+    #[serde(default)]
+    pub allow_single_package: Option<bool>,
+    /// Patterns for local lints to accept anyway, as parsed by [`LocalLintPattern::parse`].
+    #[serde(default)]
+    pub allow_local_lints: Vec<String>,
+    /// As in [`ValidationOptions::require_root_lints`].
+    #[serde(default)]
+    pub require_root_lints: Option<bool>,
+    /// The least strict level each lint may be set to in the root `[workspace.lints]`, as in
+    /// [`ValidationOptions::required_lints`], such as `{ "unsafe_code" = "deny" }`.
+    #[serde(default)]
+    pub required_lints: BTreeMap<String, LintLevel>,
+    /// As in [`ValidationOptions::reject_root_allows`].
+    #[serde(default)]
+    pub deny_allow: Option<bool>,
+    /// Globs over the lints the root may allow anyway, as in
+    /// [`ValidationOptions::permitted_root_allows`].
+    #[serde(default)]
+    pub permit_allow: Vec<String>,
+    /// As in [`ValidationOptions::check_lint_names`].
+    #[serde(default)]
+    pub check_lint_names: Option<bool>,
+    /// Optional built-in checks to run, as in [`ValidationOptions::named_checks`], such as
+    /// `["workspace-deps"]`.
+    #[serde(default)]
+    pub check: Vec<NamedCheck>,
+    /// How seriously to take each check, as in [`ValidationOptions::severities`], such as
+    /// `{ lints = "warn" }`.
+    #[serde(default)]
+    pub severity: BTreeMap<String, Severity>,
+    /// As in [`ValidationOptions::deny_warnings`].
+    #[serde(default)]
+    pub deny_warnings: Option<bool>,
+    /// Which files to scan, as in [`ValidationOptions::check_source_allows`], either
+    /// `"crate-roots"` or `"all"`.
+    #[serde(default)]
+    pub check_source_allows: Option<SourceFiles>,
+    /// The path to a [`LintPolicy`] file to check the root `[workspace.lints]` against, relative
+    /// to the workspace root.
+    ///
+    /// This isn't used by [`Config::apply_defaults`], and is left for the caller to read.
+    #[serde(default)]
+    pub policy: Option<Utf8PathBuf>,
+    /// The name of the format to write the report in, such as `json`.
+    ///
+    /// This isn't used by the library, and is left for the caller to interpret.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+impl Config {
+    /// Read the config file from the given workspace root directory.
+    ///
+    /// Returns `None` if there is no config file.
+    ///
+    /// # Errors
+    /// Returns an error if the config file exists but couldn't be read and parsed.
+    pub fn load(workspace_root: &Utf8Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(workspace_root.join(CONFIG_FILE_NAME)) {
+            Ok(contents) => toml::from_str(&contents)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the config from `workspace.metadata.workspace-lints` in the root manifest, as given in
+    /// [`cargo_metadata::Metadata::workspace_metadata`].
+    ///
+    /// Returns `None` if there is no such table.
+    ///
+    /// # Errors
+    /// Returns an error if the table isn't a valid config.
+    pub fn from_workspace_metadata(
+        workspace_metadata: &serde_json::Value,
+    ) -> Result<Option<Self>, serde_json::Error> {
+        workspace_metadata
+            .get("workspace-lints")
+            .map(Self::deserialize)
+            .transpose()
+    }
+
+    /// Fill in the options which haven't already been set with the values from this config.
+    ///
+    /// Lists (such as the required keys and excludes) are only taken from the config if `options`
+    /// has none, so that options given explicitly take precedence. A boolean option can only be
+    /// turned on: it's set if either `options` or the config sets it, and setting it to `false`
+    /// in the config is the same as leaving it out. So an option which the config turns on can't
+    /// be turned off again by `options`, only by changing the config.
+    pub fn apply_defaults(self, options: &mut ValidationOptions) {
+        if options.required_keys.is_empty() {
+            options.required_keys = self.require;
+        }
+        if options.inherited_fields.is_empty() {
+            options.inherited_fields = self.inherit;
+        }
+        if options.excludes.is_empty() {
+            options.excludes = self
+                .exclude
+                .iter()
+                .map(|pattern| ExcludePattern::parse(pattern))
+                .collect();
+        }
+        if options.allowed_local_lints.is_empty() {
+            options.allowed_local_lints = self
+                .allow_local_lints
+                .iter()
+                .map(|pattern| LocalLintPattern::parse(pattern))
+                .collect();
+        }
+        if options.required_lints.is_empty() {
+            options.required_lints = self
+                .required_lints
+                .into_iter()
+                .map(|(lint, level)| RequiredLint { lint, level })
+                .collect();
+        }
+        if options.named_checks.is_empty() {
+            options.named_checks = self.check;
+        }
+        // Severities given explicitly take precedence check by check.
+        for (check, severity) in self.severity {
+            options.severities.entry(check).or_insert(severity);
+        }
+        if options.permitted_root_allows.is_empty() {
+            options.permitted_root_allows = self.permit_allow;
+        }
+        options.include_unpublished |= self.include_unpublished.unwrap_or(false);
+        options.detect_inline_lints |= self.detect_inline_lints.unwrap_or(false);
+        options.check_priority_overrides |= self.check_priority_overrides.unwrap_or(false);
+        options.check_negating_overrides |= self.check_negating_overrides.unwrap_or(false);
+        options.check_root_lints_sorted |= self.check_root_lints_sorted.unwrap_or(false);
+        options.check_root_lint_priorities |= self.check_lint_priorities.unwrap_or(false);
+        options.check_root_lint_consistency |= self.check_lint_consistency.unwrap_or(false);
+        options.check_rustflags |= self.check_rustflags.unwrap_or(false);
+        options.check_escaped_packages |= self.check_escaped_packages.unwrap_or(false);
+        options.reject_local_lints |= self.no_local_lints.unwrap_or(false);
+        options.allow_single_package |= self.allow_single_package.unwrap_or(false);
+        options.require_root_lints |= self.require_root_lints.unwrap_or(false);
+        options.reject_root_allows |= self.deny_allow.unwrap_or(false);
+        options.check_lint_names |= self.check_lint_names.unwrap_or(false);
+        options.deny_warnings |= self.deny_warnings.unwrap_or(false);
+        options.check_source_allows = options.check_source_allows.or(self.check_source_allows);
+    }
+}
+
+/// A lint policy for the root `[workspace.lints]`, read from a standalone file so that it can be
+/// shared between (and versioned apart from) the workspaces it applies to.
+///
+/// A policy file looks like:
+///
+/// ```toml
+/// deny-allow = true
+/// permit-allow = ["clippy::module_name_repetitions"]
+///
+/// [required-lints]
+/// unsafe_code = "forbid"
+/// "clippy::unwrap_used" = "warn"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LintPolicy {
+    /// The least strict level each lint may be set to, as in
+    /// [`ValidationOptions::required_lints`]. A lint which only has to be set, at any level, can
+    /// be required at `allow`.
+    #[serde(default)]
+    pub required_lints: BTreeMap<String, LintLevel>,
+    /// Whether to reject lints set to `allow`, as in [`ValidationOptions::reject_root_allows`].
+    #[serde(default)]
+    pub deny_allow: bool,
+    /// Globs over the lints which may be allowed anyway, as in
+    /// [`ValidationOptions::permitted_root_allows`].
+    #[serde(default)]
+    pub permit_allow: Vec<String>,
+}
+
+impl LintPolicy {
+    /// Read a policy file.
+    ///
+    /// # Errors
+    /// Returns an error if the file couldn't be read and parsed.
+    pub fn load(path: &Utf8Path) -> io::Result<Self> {
+        toml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Add this policy to the checks on the root `[workspace.lints]` in `options`.
+    ///
+    /// Unlike [`Config::apply_defaults`], this adds to the options already set rather than only
+    /// filling in those which aren't, so that the policy holds whatever else is configured. A lint
+    /// which is already required must then be set to the stricter of the two levels.
+    pub fn apply(self, options: &mut ValidationOptions) {
+        for (lint, level) in self.required_lints {
+            match options
+                .required_lints
+                .iter_mut()
+                .find(|required| required.lint == lint)
+            {
+                Some(required) => required.level = required.level.max(level),
+                None => options.required_lints.push(RequiredLint { lint, level }),
+            }
+        }
+        options.reject_root_allows |= self.deny_allow;
+        options.permitted_root_allows.extend(self.permit_allow);
+    }
+}
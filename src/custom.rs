@@ -0,0 +1,61 @@
+//! Custom checks which each package in the workspace must pass.
+
+use serde::Deserialize;
+use std::fmt;
+
+use crate::{validate_package_manifest, PackageValidationErrorKind, ValidationOptions};
+
+/// A check which each package in a workspace must pass.
+///
+/// Custom checks can be added to [`ValidationOptions::checks`], to enforce other policies on the
+/// packages in a workspace alongside the built-in check, which is available as [`LintsCheck`].
+pub trait WorkspaceCheck: fmt::Debug + Send + Sync {
+    /// A short name for the check, such as `publish-false`, used when reporting failures.
+    fn name(&self) -> &str;
+
+    /// Check a single package, given its details from `cargo metadata` and its parsed manifest.
+    ///
+    /// # Errors
+    /// Returns why the package fails the check. Custom checks can use
+    /// [`PackageValidationErrorKind::CheckFailed`] for this.
+    fn check(
+        &self,
+        package: &cargo_metadata::Package,
+        manifest: &toml::Table,
+    ) -> Result<(), PackageValidationErrorKind>;
+}
+
+/// Optional built-in checks, which are turned on by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NamedCheck {
+    /// Fail packages which declare dependencies themselves that the workspace root lists in
+    /// `[workspace.dependencies]`, instead of inheriting them with `workspace = true`.
+    WorkspaceDeps,
+}
+
+/// The built-in check that packages inherit the workspace lints, with the given options.
+///
+/// This is the same check as [`validate_package_manifest`] makes, but doesn't run hooks. Checking a
+/// workspace runs this first, before [`ValidationOptions::checks`].
+#[derive(Debug, Clone, Copy)]
+pub struct LintsCheck<'a> {
+    /// The options to check packages with.
+    pub options: &'a ValidationOptions,
+    /// Whether to report whether each package passes to [`ValidationOptions::reporter`].
+    pub verbose: bool,
+}
+
+impl WorkspaceCheck for LintsCheck<'_> {
+    fn name(&self) -> &str {
+        "lints"
+    }
+
+    fn check(
+        &self,
+        package: &cargo_metadata::Package,
+        manifest: &toml::Table,
+    ) -> Result<(), PackageValidationErrorKind> {
+        validate_package_manifest(package, manifest, self.options, self.verbose)
+    }
+}
@@ -0,0 +1,101 @@
+//! Checking that members inherit the dependencies listed in `[workspace.dependencies]`.
+
+use std::collections::BTreeSet;
+
+use crate::{PackageValidationErrorKind, WorkspaceCheck};
+
+/// The kinds of dependency tables in a manifest, including the legacy spellings with `_` which
+/// cargo still accepts.
+const DEPENDENCY_TABLES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+/// Fails packages which declare a dependency themselves, even though the workspace root lists it
+/// in `[workspace.dependencies]` for them to inherit with `workspace = true`.
+///
+/// Every dependency table is checked, including those for dev and build dependencies and those
+/// under `[target.'cfg(...)']`. Dependencies renamed with `package = "..."` are matched by the
+/// package they name, as well as by their own name.
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceDependencies {
+    /// The names of the dependencies in `[workspace.dependencies]`.
+    pub(crate) names: BTreeSet<String>,
+}
+
+impl WorkspaceCheck for WorkspaceDependencies {
+    fn name(&self) -> &str {
+        "workspace-deps"
+    }
+
+    fn check(
+        &self,
+        _package: &cargo_metadata::Package,
+        manifest: &toml::Table,
+    ) -> Result<(), PackageValidationErrorKind> {
+        let targets = manifest
+            .get("target")
+            .and_then(toml::Value::as_table)
+            .into_iter()
+            .flatten()
+            .filter_map(|(target, table)| Some((format!("target.{target}."), table.as_table()?)));
+        let mut problems = Vec::new();
+        for (prefix, table) in [(String::new(), manifest)].into_iter().chain(targets) {
+            for kind in DEPENDENCY_TABLES {
+                let Some(dependencies) = table.get(*kind).and_then(toml::Value::as_table) else {
+                    continue;
+                };
+                for (name, dependency) in dependencies {
+                    // The name the dependency would be inherited under.
+                    let Some(inherited) = [Some(name.as_str()), package(dependency)]
+                        .into_iter()
+                        .flatten()
+                        .find(|name| self.names.contains(*name))
+                    else {
+                        continue;
+                    };
+                    if dependency.get("workspace") == Some(&toml::Value::Boolean(true)) {
+                        continue;
+                    }
+                    problems.push(format!(
+                        "`{name}` in `[{prefix}{kind}]` is declared {} instead of with \
+                         `{inherited}.workspace = true`",
+                        declaration(dependency)
+                    ));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(PackageValidationErrorKind::CheckFailed {
+                check: self.name().to_owned(),
+                reason: problems.join("; "),
+            })
+        }
+    }
+}
+
+/// Describe how a dependency is declared, such as with its version.
+fn declaration(dependency: &toml::Value) -> String {
+    if let Some(version) = dependency
+        .as_str()
+        .or_else(|| dependency.get("version")?.as_str())
+    {
+        format!("with version `{version}`")
+    } else if dependency.get("path").is_some() {
+        "with a path".to_owned()
+    } else if dependency.get("git").is_some() {
+        "from git".to_owned()
+    } else {
+        "locally".to_owned()
+    }
+}
+
+/// The package a dependency renames with `package = "..."`, if it does.
+fn package(dependency: &toml::Value) -> Option<&str> {
+    dependency.get("package")?.as_str()
+}
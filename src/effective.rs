@@ -0,0 +1,113 @@
+//! Resolving the lints each workspace member actually gets.
+
+use cargo_metadata::{camino::Utf8PathBuf, Metadata};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{policy, root, WorkspaceValidationError};
+
+/// The lints a workspace member gets, after inheriting the workspace lints.
+#[derive(Debug, Clone)]
+pub struct PackageLints {
+    /// The name of the package.
+    pub name: String,
+    /// The path to the package's `Cargo.toml` manifest.
+    pub manifest_path: Utf8PathBuf,
+    /// Whether the package sets `lints.workspace = true`.
+    pub inherits: bool,
+    /// The lints, ordered by priority, since those with a higher priority take precedence.
+    pub lints: Vec<ResolvedLint>,
+}
+
+/// A lint set for a package, as in [`PackageLints`].
+#[derive(Debug, Clone)]
+pub struct ResolvedLint {
+    /// The lint, with the tool's prefix for tools other than `rustc` (such as
+    /// `clippy::unwrap_used`).
+    pub lint: String,
+    /// The level the lint is set to, as written (such as `deny`).
+    pub level: String,
+    /// The lint's `priority`, which defaults to 0.
+    pub priority: i64,
+    /// The manifest which sets the lint: the workspace root's for inherited lints, or the
+    /// package's own.
+    pub source: Utf8PathBuf,
+}
+
+/// Resolve the lints each workspace member gets.
+///
+/// Members which set `lints.workspace = true` get the root `[workspace.lints]`, and any lints in
+/// their own `[lints]` tables take precedence over those. Members which don't only get their own.
+///
+/// # Errors
+/// Returns an error if a manifest couldn't be read or parsed.
+pub fn package_lints(metadata: &Metadata) -> Result<Vec<PackageLints>, WorkspaceValidationError> {
+    let root_manifest_path = metadata.workspace_root.join("Cargo.toml");
+    let workspace_lints = root::read_workspace_lints(&metadata.workspace_root)?.unwrap_or_default();
+    let workspace_members = metadata.workspace_members.iter().collect::<HashSet<_>>();
+    let mut packages = Vec::new();
+    for package in metadata
+        .packages
+        .iter()
+        .filter(|package| workspace_members.contains(&package.id))
+    {
+        let path = &package.manifest_path;
+        let manifest: toml::Table =
+            toml::from_str(&root::read_manifest(path)?).map_err(|error| {
+                WorkspaceValidationError::Toml {
+                    path: path.clone(),
+                    error,
+                }
+            })?;
+        let own_lints = manifest
+            .get("lints")
+            .and_then(toml::Value::as_table)
+            .cloned()
+            .unwrap_or_default();
+        let inherits = own_lints.get("workspace") == Some(&toml::Value::Boolean(true));
+        // Each lint's config and source, keyed by tool and name.
+        let mut lints = BTreeMap::new();
+        let mut add = |tables: &toml::Table, source: &Utf8PathBuf| {
+            let tools = tables
+                .iter()
+                .filter_map(|(tool, lints)| Some((tool, lints.as_table()?)));
+            for (tool, tool_lints) in tools {
+                for (lint, config) in tool_lints {
+                    lints.insert(
+                        (tool.clone(), lint.clone()),
+                        (config.clone(), source.clone()),
+                    );
+                }
+            }
+        };
+        if inherits {
+            add(&workspace_lints, &root_manifest_path);
+        }
+        add(&own_lints, path);
+        let mut lints = lints
+            .into_iter()
+            .map(|((tool, lint), (config, source))| ResolvedLint {
+                lint: if tool == "rust" {
+                    lint
+                } else {
+                    format!("{tool}::{lint}")
+                },
+                level: config
+                    .as_str()
+                    .or_else(|| config.get("level")?.as_str())
+                    .unwrap_or("?")
+                    .to_owned(),
+                priority: policy::priority(&config),
+                source,
+            })
+            .collect::<Vec<_>>();
+        // Order by priority as Cargo does, keeping lints of the same priority sorted by name.
+        lints.sort_by_key(|lint| lint.priority);
+        packages.push(PackageLints {
+            name: package.name.clone(),
+            manifest_path: path.clone(),
+            inherits,
+            lints,
+        });
+    }
+    Ok(packages)
+}
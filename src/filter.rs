@@ -0,0 +1,130 @@
+//! Patterns for excluding packages from validation.
+
+use cargo_metadata::camino::Utf8Path;
+use std::path::Path;
+
+use crate::path::relative_path;
+
+/// A pattern describing packages which should not be checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExcludePattern {
+    /// Exclude the package with exactly this name.
+    Name(String),
+    /// Exclude all packages whose name matches this glob (`*` and `?` are supported).
+    Glob(String),
+    /// Exclude all packages whose manifest lives under this directory, relative to the workspace
+    /// root. Directories outside the workspace root can be given with leading `..` components.
+    PathPrefix(String),
+}
+
+impl ExcludePattern {
+    /// Parse a single exclude pattern.
+    ///
+    /// Patterns containing a `/` are treated as path prefixes relative to the workspace root,
+    /// patterns containing `*` or `?` are treated as globs over the package name, and anything
+    /// else must match the package name exactly.
+    #[must_use]
+    pub fn parse(pattern: &str) -> Self {
+        if pattern.contains('/') {
+            Self::PathPrefix(pattern.trim_end_matches('/').to_owned())
+        } else if pattern.contains(['*', '?']) {
+            Self::Glob(pattern.to_owned())
+        } else {
+            Self::Name(pattern.to_owned())
+        }
+    }
+
+    /// Parse the contents of an exclude file.
+    ///
+    /// The file contains one pattern per line (see [`ExcludePattern::parse`]). Blank lines are
+    /// ignored, as is anything following a `#`.
+    #[must_use]
+    pub fn parse_file(contents: &str) -> Vec<Self> {
+        contents
+            .lines()
+            .map(|line| {
+                line.split_once('#')
+                    .map_or(line, |(pattern, _)| pattern)
+                    .trim()
+            })
+            .filter(|line| !line.is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Check whether this pattern excludes the given package.
+    ///
+    /// # Arguments
+    /// * `name`: The name of the package.
+    /// * `manifest_path`: The path to the package's `Cargo.toml` manifest.
+    /// * `workspace_root`: The root directory of the workspace, which path prefixes are relative
+    ///   to.
+    #[must_use]
+    pub fn matches(&self, name: &str, manifest_path: &Utf8Path, workspace_root: &Utf8Path) -> bool {
+        match self {
+            Self::Name(pattern) => name == pattern,
+            Self::Glob(glob) => glob_matches(glob.as_bytes(), name.as_bytes()),
+            Self::PathPrefix(prefix) => manifest_path.parent().is_some_and(|dir| {
+                relative_path(dir, workspace_root)
+                    .as_std_path()
+                    .starts_with(Path::new(prefix))
+            }),
+        }
+    }
+}
+
+/// Match `text` against a glob `pattern`, where `*` matches any run of characters and `?` matches
+/// any single character.
+pub(crate) fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| glob_matches(rest, &text[skip..])),
+        Some((b'?', rest)) => !text.is_empty() && glob_matches(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && glob_matches(rest, &text[1..]),
+    }
+}
+
+/// A pattern for lints which packages may set in their own `[lints]` tables, even when
+/// [`ValidationOptions::reject_local_lints`](crate::ValidationOptions::reject_local_lints) is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalLintPattern {
+    /// A glob over the names of the packages this applies to, or `None` for every package.
+    package: Option<String>,
+    /// A glob over the lints this allows, such as `clippy::float_cmp` or `clippy::*`.
+    lint: String,
+}
+
+impl LocalLintPattern {
+    /// Parse a single pattern, of the form `[PACKAGE/]LINT`.
+    ///
+    /// Both parts may be globs (`*` and `?` are supported). Lints from tools other than `rustc`
+    /// are given with the tool's prefix as in source code (such as `clippy::float_cmp`), so
+    /// `my-crate/*` allows every local lint in `my-crate`.
+    #[must_use]
+    pub fn parse(pattern: &str) -> Self {
+        match pattern.split_once('/') {
+            Some((package, lint)) => Self {
+                package: Some(package.to_owned()),
+                lint: lint.to_owned(),
+            },
+            None => Self {
+                package: None,
+                lint: pattern.to_owned(),
+            },
+        }
+    }
+
+    /// Check whether this pattern allows the package with the given name to set `lint` for
+    /// `tool` in its own `[lints]` tables.
+    #[must_use]
+    pub fn matches(&self, package: &str, tool: &str, lint: &str) -> bool {
+        let lint = if tool == "rust" {
+            lint.to_owned()
+        } else {
+            format!("{tool}::{lint}")
+        };
+        self.package.as_ref().map_or(true, |glob| {
+            glob_matches(glob.as_bytes(), package.as_bytes())
+        }) && glob_matches(self.lint.as_bytes(), lint.as_bytes())
+    }
+}
@@ -0,0 +1,726 @@
+//! Alternative formats for reporting the results of a check.
+
+use cargo_metadata::camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use std::{collections::BTreeMap, env, fmt::Write as _, fs, io, ops::Range, slice};
+
+use crate::{
+    autofix, json::FailureKind, path::relative_path, FalseFix, PackageResult, PackageStatus,
+    PackageValidationErrorKind, WorkspaceReport,
+};
+
+/// Write the results as a directory tree of the workspace, marking each package with its status.
+///
+/// Paths are relative to the workspace root, if it is known.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_tree(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    let mut root = TreeNode::default();
+    for result in &report.packages {
+        let result = &result.result;
+        let dir = package_dir(report, result);
+        let node = dir.components().fold(&mut root, |node, component| {
+            node.children
+                .entry(component.as_str().to_owned())
+                .or_default()
+        });
+        node.packages.push(result);
+    }
+    let root_name = report
+        .workspace_root
+        .as_ref()
+        .map_or(".", |workspace_root| workspace_root.as_str());
+    writeln!(out, "{root_name}{}", root.annotation(root_name))?;
+    root.write_children(out, "")
+}
+
+/// Write the number of passing packages under each top-level directory of the workspace.
+///
+/// Packages are grouped by the first component of their directory relative to the workspace
+/// root (or `.` for packages at the root itself), and skipped packages aren't counted.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_dir_summary(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    let mut counts = BTreeMap::<String, (usize, usize)>::new();
+    for result in &report.packages {
+        let result = &result.result;
+        let passed = match result.status {
+            PackageStatus::Passed => true,
+            PackageStatus::Failed(_) => false,
+            PackageStatus::Skipped(_) => continue,
+        };
+        let dir = package_dir(report, result);
+        let top_level = dir
+            .components()
+            .next()
+            .map_or_else(|| ".".to_owned(), |component| component.as_str().to_owned());
+        let (passing, total) = counts.entry(top_level).or_default();
+        *passing += usize::from(passed);
+        *total += 1;
+    }
+    writeln!(out, "Passing packages by directory:")?;
+    for (dir, (passing, total)) in counts {
+        writeln!(out, "  {dir}: {passing}/{total} passing")?;
+    }
+    Ok(())
+}
+
+/// Write how many packages pass, fail in each way, and were skipped, with the percentage of the
+/// checked packages which pass.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_stats(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    let (mut passed, mut missing, mut wrong_value, mut other, mut skipped) = (0, 0, 0, 0, 0);
+    for result in &report.packages {
+        match &result.result.status {
+            PackageStatus::Passed => passed += 1,
+            PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsMissing) => {
+                missing += 1;
+            }
+            PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsWrongValue(_)) => {
+                wrong_value += 1;
+            }
+            PackageStatus::Failed(_) => other += 1,
+            PackageStatus::Skipped(_) => skipped += 1,
+        }
+    }
+    let checked = passed + missing + wrong_value + other;
+    if checked == 0 {
+        writeln!(out, "Coverage: no packages were checked")?;
+    } else {
+        // Tenths of a percent, rounded down so that 100% means every package passes.
+        let permille = passed * 1000 / checked;
+        writeln!(
+            out,
+            "Coverage: {passed} of {checked} checked packages pass ({}.{}%)",
+            permille / 10,
+            permille % 10
+        )?;
+    }
+    writeln!(out, "  passed: {passed}")?;
+    writeln!(out, "  missing `lints.workspace`: {missing}")?;
+    writeln!(out, "  wrong value of `lints.workspace`: {wrong_value}")?;
+    writeln!(out, "  other failures: {other}")?;
+    writeln!(out, "  skipped: {skipped}")
+}
+
+/// The directory containing the package's manifest, relative to the workspace root if known.
+fn package_dir(report: &WorkspaceReport, result: &PackageResult) -> Utf8PathBuf {
+    let dir = result.manifest_path.parent().unwrap_or(Utf8Path::new(""));
+    match &report.workspace_root {
+        Some(workspace_root) => relative_path(dir, workspace_root),
+        None => dir.to_owned(),
+    }
+}
+
+/// A directory in the tree written by [`write_tree`].
+#[derive(Default)]
+struct TreeNode<'a> {
+    /// The subdirectories containing packages, by name.
+    children: BTreeMap<String, TreeNode<'a>>,
+    /// The packages whose manifests are directly in this directory.
+    packages: Vec<&'a PackageResult>,
+}
+
+impl TreeNode<'_> {
+    /// The statuses of the packages in this directory, to write after its name.
+    ///
+    /// Packages are only named if their name differs from the directory's.
+    fn annotation(&self, dir_name: &str) -> String {
+        self.packages
+            .iter()
+            .map(|package| {
+                let marker = match package.status {
+                    PackageStatus::Passed => "[PASS]",
+                    PackageStatus::Failed(_) => "[FAIL]",
+                    PackageStatus::Skipped(_) => "[SKIP]",
+                };
+                if package.name == dir_name {
+                    format!(" {marker}")
+                } else {
+                    format!(" {marker} {}", package.name)
+                }
+            })
+            .collect()
+    }
+
+    /// Write the subdirectories of this directory, each line starting with `prefix`.
+    fn write_children(&self, out: &mut dyn io::Write, prefix: &str) -> io::Result<()> {
+        let mut children = self.children.iter().peekable();
+        while let Some((name, child)) = children.next() {
+            let (branch, continuation) = if children.peek().is_some() {
+                ("├── ", "│   ")
+            } else {
+                ("└── ", "    ")
+            };
+            writeln!(out, "{prefix}{branch}{name}{}", child.annotation(name))?;
+            child.write_children(out, &format!("{prefix}{continuation}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a Markdown summary of the results, with the number of packages in each status and a
+/// table of the failing packages.
+///
+/// This is suitable for appending to a GitHub Actions job summary.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_markdown_summary(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+    for result in &report.packages {
+        match result.result.status {
+            PackageStatus::Passed => passed += 1,
+            PackageStatus::Failed(_) => failed += 1,
+            PackageStatus::Skipped(_) => skipped += 1,
+        }
+    }
+    writeln!(out, "## Workspace lints\n")?;
+    writeln!(out, "| Passed | Failed | Skipped |")?;
+    writeln!(out, "| --- | --- | --- |")?;
+    writeln!(out, "| {passed} | {failed} | {skipped} |")?;
+    if failed > 0 {
+        writeln!(out, "\n### Failing packages\n")?;
+        writeln!(out, "| Package | Manifest | Problem |")?;
+        writeln!(out, "| --- | --- | --- |")?;
+        for result in &report.packages {
+            let result = &result.result;
+            let PackageStatus::Failed(kind) = &result.status else {
+                continue;
+            };
+            let manifest_path = match &report.workspace_root {
+                Some(workspace_root) => relative_path(&result.manifest_path, workspace_root),
+                None => result.manifest_path.clone(),
+            };
+            writeln!(
+                out,
+                "| {} | `{manifest_path}` | {} |",
+                markdown_cell(&result.name),
+                markdown_cell(&kind.to_string()),
+            )?;
+        }
+    }
+    writeln!(out)
+}
+
+/// Escape `text` to be written in a single cell of a Markdown table.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Write a GitHub Actions `::error` workflow command for each failing package, so that the
+/// failures are shown as annotations on their manifests.
+///
+/// Paths are relative to the workspace root, if it is known. Where possible, each annotation
+/// points at the line of the manifest with the problem, and otherwise at the first line.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_github_annotations(
+    out: &mut dyn io::Write,
+    report: &WorkspaceReport,
+) -> io::Result<()> {
+    for result in &report.packages {
+        let result = &result.result;
+        let PackageStatus::Failed(kind) = &result.status else {
+            continue;
+        };
+        let manifest_path = match &report.workspace_root {
+            Some(workspace_root) => relative_path(&result.manifest_path, workspace_root),
+            None => result.manifest_path.clone(),
+        };
+        let line = fs::read_to_string(&result.manifest_path)
+            .ok()
+            .and_then(|contents| {
+                let span = failure_span(&contents, kind)?;
+                Some(line_number(&contents, span.start))
+            })
+            .unwrap_or(1);
+        writeln!(
+            out,
+            "::error file={},line={line}::{}",
+            escape_workflow_property(manifest_path.as_str()),
+            escape_workflow_data(&format!("Package {}: {kind}", result.name)),
+        )?;
+    }
+    Ok(())
+}
+
+/// Write a SARIF log of the failing packages, for uploading to code scanning tools such as
+/// GitHub's.
+///
+/// Each failure is located in its manifest at the offending value or syntax error where there is
+/// one, and otherwise at the `[lints]` tables, or the `[package]` header if there are none. Paths
+/// are relative to the workspace root, if it is known.
+///
+/// Warnings from advisory checks are included at the `warning` level, located at the manifest of
+/// the package they're about, or the root manifest for those about the workspace.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_sarif(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    write_sarif_log(out, slice::from_ref(report))
+}
+
+/// Write a single SARIF log for several workspaces, with a run for each, as in [`write_sarif`].
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_sarif_log(out: &mut dyn io::Write, reports: &[WorkspaceReport]) -> io::Result<()> {
+    let log = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": reports.iter().map(sarif_run).collect::<Vec<_>>(),
+    });
+    serde_json::to_writer_pretty(&mut *out, &log)?;
+    writeln!(out)
+}
+
+/// The SARIF run for the results of checking a workspace.
+fn sarif_run(report: &WorkspaceReport) -> serde_json::Value {
+    let mut rules = BTreeMap::new();
+    let mut results = Vec::new();
+    for result in &report.packages {
+        let result = &result.result;
+        let PackageStatus::Failed(kind) = &result.status else {
+            continue;
+        };
+        let rule_id = FailureKind::new(kind).kind;
+        rules.entry(rule_id.clone()).or_insert_with(|| {
+            serde_json::json!({
+                "id": rule_id,
+                "help": { "text": kind.suggestion() },
+            })
+        });
+        let uri = artifact_uri(report, &result.manifest_path);
+        let region = fs::read_to_string(&result.manifest_path)
+            .ok()
+            .and_then(|contents| {
+                let span = failure_span(&contents, kind)?;
+                Some(serde_json::json!({
+                    "startLine": line_number(&contents, span.start),
+                    "endLine": line_number(&contents, span.end.max(span.start + 1) - 1),
+                    "byteOffset": span.start,
+                    "byteLength": span.len(),
+                }))
+            })
+            .unwrap_or_else(|| serde_json::json!({ "startLine": 1 }));
+        results.push(serde_json::json!({
+            "ruleId": rule_id,
+            "level": "error",
+            "message": { "text": format!("Package {}: {kind}", result.name) },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": region,
+                },
+            }],
+        }));
+    }
+    // Warnings are located at the manifest of the package they're about, or the root manifest.
+    let workspace_manifest = report
+        .workspace_root
+        .as_ref()
+        .map(|workspace_root| workspace_root.join("Cargo.toml"));
+    let warnings = report
+        .warnings
+        .iter()
+        .map(|warning| (workspace_manifest.as_deref(), warning))
+        .chain(report.packages.iter().flat_map(|result| {
+            let manifest_path = Some(&*result.result.manifest_path);
+            result
+                .result
+                .warnings
+                .iter()
+                .map(move |warning| (manifest_path, warning))
+        }));
+    for (manifest_path, warning) in warnings {
+        rules
+            .entry(warning.code.clone())
+            .or_insert_with(|| serde_json::json!({ "id": warning.code }));
+        let locations = manifest_path
+            .map(|manifest_path| {
+                serde_json::json!({
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": artifact_uri(report, manifest_path) },
+                        "region": { "startLine": 1 },
+                    },
+                })
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        results.push(serde_json::json!({
+            "ruleId": warning.code,
+            "level": "warning",
+            "message": { "text": warning.message },
+            "locations": locations,
+        }));
+    }
+    serde_json::json!({
+        "tool": {
+            "driver": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                "rules": rules.into_values().collect::<Vec<_>>(),
+            },
+        },
+        "results": results,
+    })
+}
+
+/// The URI of a file for a SARIF log, which is relative to the workspace root if it is known.
+///
+/// Otherwise, this is a `file` URI of the absolute path, since there'd be nothing to resolve a
+/// relative path (such as one given to `--members`) against.
+fn artifact_uri(report: &WorkspaceReport, path: &Utf8Path) -> String {
+    if let Some(workspace_root) = &report.workspace_root {
+        return encode_uri_path(relative_path(path, workspace_root).as_str());
+    }
+    let path = env::current_dir()
+        .ok()
+        .and_then(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+        .map_or_else(|| path.to_owned(), |dir| dir.join(path));
+    if path.is_absolute() {
+        format!("file://{}", encode_uri_path(path.as_str()))
+    } else {
+        encode_uri_path(path.as_str())
+    }
+}
+
+/// Percent-encode a path for use in a URI, leaving only unreserved characters and separators.
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            // Writing to a `String` can't fail.
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Write a `JUnit` XML report, with a test case for each package, for CI systems which show test
+/// results.
+///
+/// Each failure gives its kind as the type and the package's manifest path, which is relative to
+/// the workspace root if it is known. Skipped packages are marked as skipped, with the reason.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_junit(out: &mut dyn io::Write, report: &WorkspaceReport) -> io::Result<()> {
+    write_junit_suites(out, slice::from_ref(report))
+}
+
+/// Write a single `JUnit` XML report for several workspaces, as in [`write_junit`].
+///
+/// Each workspace is a test suite, named by its root if there are several.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_junit_suites(out: &mut dyn io::Write, reports: &[WorkspaceReport]) -> io::Result<()> {
+    let count = |f: fn(&PackageStatus) -> bool| {
+        reports
+            .iter()
+            .flat_map(|report| &report.packages)
+            .filter(|result| f(&result.result.status))
+            .count()
+    };
+    let tests = count(|_| true);
+    let failures = count(|status| matches!(status, PackageStatus::Failed(_)));
+    let skipped = count(|status| matches!(status, PackageStatus::Skipped(_)));
+    let name = escape_xml(env!("CARGO_PKG_NAME"));
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="{name}" tests="{tests}" failures="{failures}" skipped="{skipped}">"#,
+    )?;
+    for report in reports {
+        let suite = match &report.workspace_root {
+            Some(workspace_root) if reports.len() > 1 => escape_xml(workspace_root.as_str()),
+            _ => name.clone(),
+        };
+        write_junit_suite(out, report, &suite)?;
+    }
+    writeln!(out, "</testsuites>")
+}
+
+/// Write the `JUnit` test suite for the results of checking a workspace, with the given name.
+fn write_junit_suite(
+    out: &mut dyn io::Write,
+    report: &WorkspaceReport,
+    suite: &str,
+) -> io::Result<()> {
+    let count = |f: fn(&PackageStatus) -> bool| {
+        report
+            .packages
+            .iter()
+            .filter(|result| f(&result.result.status))
+            .count()
+    };
+    let failures = count(|status| matches!(status, PackageStatus::Failed(_)));
+    let skipped = count(|status| matches!(status, PackageStatus::Skipped(_)));
+    writeln!(
+        out,
+        r#"  <testsuite name="{suite}" tests="{}" failures="{failures}" skipped="{skipped}">"#,
+        report.packages.len(),
+    )?;
+    for result in &report.packages {
+        let result = &result.result;
+        write!(
+            out,
+            r#"    <testcase name="{}" classname="{suite}""#,
+            escape_xml(&result.name),
+        )?;
+        match &result.status {
+            PackageStatus::Passed => writeln!(out, "/>")?,
+            PackageStatus::Skipped(reason) => writeln!(
+                out,
+                ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                escape_xml(&format!("Package {} {reason}", result.name)),
+            )?,
+            PackageStatus::Failed(kind) => {
+                let manifest_path = match &report.workspace_root {
+                    Some(workspace_root) => relative_path(&result.manifest_path, workspace_root),
+                    None => result.manifest_path.clone(),
+                };
+                writeln!(
+                    out,
+                    ">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>",
+                    escape_xml(&FailureKind::new(kind).kind),
+                    escape_xml(&kind.to_string()),
+                    escape_xml(&format!(
+                        "Package {}: {kind}\nManifest: {manifest_path}\nSuggestion: {}",
+                        result.name,
+                        kind.suggestion()
+                    )),
+                )?;
+            }
+        }
+    }
+    writeln!(out, "  </testsuite>")
+}
+
+/// Find the byte range of the part of a manifest responsible for a package failing.
+///
+/// This is the offending value or syntax error where there is one, and otherwise the `[lints]`
+/// tables, or the `[package]` header if there are none.
+fn failure_span(contents: &str, kind: &PackageValidationErrorKind) -> Option<Range<usize>> {
+    match kind {
+        PackageValidationErrorKind::WorkspaceLintsWrongValue(_) => {
+            if let Some(workspace) = toml::from_str::<SpannedManifest>(contents)
+                .ok()
+                .and_then(|manifest| manifest.lints?.workspace)
+            {
+                return Some(workspace.span());
+            }
+        }
+        PackageValidationErrorKind::ManifestParseError(e) => return e.span(),
+        PackageValidationErrorKind::DuplicateLintsTable { second_line, .. } => {
+            return table_headers(contents)
+                .find(|(line, _, _)| line == second_line)
+                .map(|(_, span, _)| span);
+        }
+        _ => {}
+    }
+    let mut headers = table_headers(contents).skip_while(|(_, _, name)| !is_lints_table(name));
+    if let Some((_, first, _)) = headers.next() {
+        let end = headers
+            .find(|(_, _, name)| !is_lints_table(name))
+            .map_or(contents.len(), |(_, span, _)| span.start);
+        return Some(first.start..first.start + contents[first.start..end].trim_end().len());
+    }
+    table_headers(contents)
+        .find(|(_, _, name)| name == "package")
+        .map(|(_, span, _)| span)
+}
+
+/// Check whether a table header names `[lints]` or one of its subtables.
+fn is_lints_table(name: &str) -> bool {
+    name == "lints" || name.starts_with("lints.")
+}
+
+/// The table headers in a raw manifest, with their (1-indexed) line numbers, the byte ranges of
+/// the header lines, and the names of the tables.
+fn table_headers(contents: &str) -> impl Iterator<Item = (usize, Range<usize>, String)> + '_ {
+    let mut offset = 0;
+    contents
+        .split_inclusive('\n')
+        .enumerate()
+        .filter_map(move |(index, raw_line)| {
+            let start = offset;
+            offset += raw_line.len();
+            let line = raw_line
+                .split_once('#')
+                .map_or(raw_line, |(line, _)| line)
+                .trim();
+            let name = line.strip_prefix('[')?.strip_suffix(']')?;
+            // Arrays of tables, such as `[[bin]]`, have a second pair of brackets.
+            let name = name
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+                .unwrap_or(name);
+            let name = name.split('.').map(str::trim).collect::<Vec<_>>().join(".");
+            Some((index + 1, start..start + raw_line.trim_end().len(), name))
+        })
+}
+
+/// The (1-indexed) line number of the given byte offset in `contents`.
+fn line_number(contents: &str, offset: usize) -> usize {
+    contents
+        .get(..offset)
+        .map_or(1, |before| before.matches('\n').count() + 1)
+}
+
+/// Just enough of a manifest to find where `lints.workspace` is set.
+#[derive(Deserialize)]
+struct SpannedManifest {
+    lints: Option<SpannedLints>,
+}
+
+/// The `[lints]` table of a [`SpannedManifest`].
+#[derive(Deserialize)]
+struct SpannedLints {
+    workspace: Option<toml::Spanned<toml::Value>>,
+}
+
+/// Escape text for use in XML content or attribute values.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Escape the message of a GitHub Actions workflow command.
+fn escape_workflow_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a GitHub Actions workflow command.
+fn escape_workflow_property(text: &str) -> String {
+    escape_workflow_data(text)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// The lines of unchanged context around each change in [`write_patch`].
+const PATCH_CONTEXT: usize = 3;
+
+/// Write the edits which would fix the failing packages (as planned by
+/// [`plan_fixes`](crate::plan_fixes)) as a unified diff, without making them.
+///
+/// Paths are relative to the workspace root if it is known, so the patch can be applied from
+/// there with `git apply`. Manifests which couldn't be read or edited are left out.
+///
+/// `git apply` rejects paths outside the directory it's run in, so manifests outside the
+/// workspace root (or absolute paths, if the root isn't known) are left out too. These are
+/// returned, so that they can be fixed some other way.
+///
+/// # Errors
+/// Returns an error if writing to `out` fails.
+pub fn write_patch(
+    out: &mut dyn io::Write,
+    report: &WorkspaceReport,
+    false_fix: FalseFix,
+) -> io::Result<Vec<Utf8PathBuf>> {
+    let plan = autofix::plan_fixes(
+        report.packages.iter().map(|result| &result.result),
+        false_fix,
+    );
+    let mut outside = Vec::new();
+    for fix in &plan {
+        let (Ok(old), Ok(new)) = (
+            fs::read_to_string(&fix.manifest_path),
+            autofix::fixed_manifest(fix),
+        ) else {
+            continue;
+        };
+        let path = report.workspace_root.as_deref().map_or_else(
+            || fix.manifest_path.clone(),
+            |workspace_root| relative_path(&fix.manifest_path, workspace_root),
+        );
+        if path
+            .components()
+            .any(|component| !matches!(component, Utf8Component::Normal(_)))
+        {
+            outside.push(fix.manifest_path.clone());
+            continue;
+        }
+        write_file_diff(out, &path, &old, &new)?;
+    }
+    Ok(outside)
+}
+
+/// Write the difference between the old and new contents of the file at `path` as a single hunk
+/// of a unified diff, covering the lines from the first change to the last.
+fn write_file_diff(
+    out: &mut dyn io::Write,
+    path: &Utf8Path,
+    old: &str,
+    new: &str,
+) -> io::Result<()> {
+    // Keep the line endings, so that a missing newline at the end of the file can be marked.
+    let old = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new = new.split_inclusive('\n').collect::<Vec<_>>();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    if prefix == old.len() && prefix == new.len() {
+        return Ok(());
+    }
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let start = prefix.saturating_sub(PATCH_CONTEXT);
+    let old_end = (old.len() - suffix + PATCH_CONTEXT).min(old.len());
+    let new_end = (new.len() - suffix + PATCH_CONTEXT).min(new.len());
+    // Hunks without any lines of a side are numbered from the line before them.
+    let line_number = |len: usize| if len == 0 { start } else { start + 1 };
+    writeln!(out, "diff --git a/{path} b/{path}")?;
+    writeln!(out, "--- a/{path}")?;
+    writeln!(out, "+++ b/{path}")?;
+    writeln!(
+        out,
+        "@@ -{},{} +{},{} @@",
+        line_number(old_end - start),
+        old_end - start,
+        line_number(new_end - start),
+        new_end - start
+    )?;
+    let lines = old[start..prefix]
+        .iter()
+        .map(|line| (' ', line))
+        .chain(
+            old[prefix..old.len() - suffix]
+                .iter()
+                .map(|line| ('-', line)),
+        )
+        .chain(
+            new[prefix..new.len() - suffix]
+                .iter()
+                .map(|line| ('+', line)),
+        )
+        .chain(
+            old[old.len() - suffix..old_end]
+                .iter()
+                .map(|line| (' ', line)),
+        );
+    for (marker, line) in lines {
+        write!(out, "{marker}{line}")?;
+        if !line.ends_with('\n') {
+            writeln!(out, "\n\\ No newline at end of file")?;
+        }
+    }
+    Ok(())
+}
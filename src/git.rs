@@ -0,0 +1,69 @@
+//! Finding the manifests changed in a git repository.
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use std::{io, process::Command};
+
+use crate::{WorkspaceValidationError, CONFIG_FILE_NAME};
+
+/// Find the `Cargo.toml` manifests which have changed in the git repository containing `dir`.
+///
+/// With a `base` ref, these are the manifests changed on `HEAD` since it branched from `base`
+/// (as in `git diff base...HEAD`). Otherwise, they are the manifests with staged changes.
+/// Deleted manifests aren't included. A changed [`CONFIG_FILE_NAME`] counts as a change to the
+/// `Cargo.toml` beside it, since it configures the checks for that workspace.
+///
+/// # Errors
+/// Returns an error if `git` couldn't be run, or failed (such as if `dir` isn't in a git
+/// repository, or `base` isn't a known ref).
+pub fn changed_manifests(
+    dir: &Utf8Path,
+    base: Option<&str>,
+) -> Result<Vec<Utf8PathBuf>, WorkspaceValidationError> {
+    let toplevel = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let range = base.map(|base| format!("{base}...HEAD"));
+    let mut args = vec!["diff", "--name-only", "--diff-filter=d", "-z"];
+    match &range {
+        Some(range) => args.push(range),
+        None => args.push("--cached"),
+    }
+    args.push("--");
+    let toplevel = Utf8Path::new(toplevel.trim_end());
+    let mut manifests = Vec::new();
+    for path in git(dir, &args)?.split('\0').map(Utf8Path::new) {
+        let manifest_path = match path.file_name() {
+            Some("Cargo.toml") => toplevel.join(path),
+            Some(CONFIG_FILE_NAME) => toplevel.join(path.with_file_name("Cargo.toml")),
+            _ => continue,
+        };
+        if !manifests.contains(&manifest_path) {
+            manifests.push(manifest_path);
+        }
+    }
+    Ok(manifests)
+}
+
+/// Run `git` in `dir` with the given arguments, returning its output.
+fn git(dir: &Utf8Path, args: &[&str]) -> Result<String, WorkspaceValidationError> {
+    let failed = |error| WorkspaceValidationError::Io {
+        path: "git".into(),
+        error,
+    };
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(failed)?;
+    if !output.status.success() {
+        return Err(failed(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "`git {}` failed ({}): {}",
+                args.join(" "),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            ),
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
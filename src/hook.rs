@@ -0,0 +1,75 @@
+//! Running external commands as additional per-package checks.
+
+use cargo_metadata::camino::Utf8Path;
+use std::process::{Command, Stdio};
+
+use crate::{style, PackageValidationErrorKind, ValidationOptions, WorkspaceValidationError};
+
+/// Run each hook against the given package, stopping at the first one which fails.
+///
+/// Each hook is split on whitespace into a program and its arguments, and is run with the path to
+/// the package's manifest as a final argument, along with these environment variables:
+/// * `CARGO_WORKSPACE_LINTS_PACKAGE_NAME`: The name of the package.
+/// * `CARGO_WORKSPACE_LINTS_MANIFEST_PATH`: The path to the package's manifest.
+/// * `CARGO_WORKSPACE_LINTS_WORKSPACE_ROOT`: The root of the workspace, if it is known.
+///
+/// A hook's output (both stdout and stderr) is captured and, if the hook fails or the check is
+/// verbose, passed on to [`ValidationOptions::reporter`] in one piece once it exits. So it can't
+/// end up in a report written to stdout, or be interleaved with the output of hooks run in
+/// parallel.
+///
+/// # Errors
+/// Returns an error, naming the hook's program, if a hook couldn't be run at all. A hook which runs
+/// but exits unsuccessfully is reported as a failure of the package, in the inner result.
+pub(crate) fn run_hooks(
+    name: &str,
+    manifest_path: &Utf8Path,
+    workspace_root: &Utf8Path,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Result<(), PackageValidationErrorKind>, WorkspaceValidationError> {
+    for hook in &options.hooks {
+        let mut words = hook.split_whitespace();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let mut command = Command::new(program);
+        command
+            .args(words)
+            .arg(manifest_path)
+            .env("CARGO_WORKSPACE_LINTS_PACKAGE_NAME", name)
+            .env("CARGO_WORKSPACE_LINTS_MANIFEST_PATH", manifest_path);
+        if !workspace_root.as_str().is_empty() {
+            command.env("CARGO_WORKSPACE_LINTS_WORKSPACE_ROOT", workspace_root);
+        }
+        let output = command.stdin(Stdio::null()).output().map_err(|error| {
+            WorkspaceValidationError::Io {
+                path: program.into(),
+                error,
+            }
+        })?;
+        let hook_output = [&output.stdout[..], &output.stderr[..]]
+            .into_iter()
+            .map(String::from_utf8_lossy)
+            .filter(|text| !text.trim().is_empty())
+            .map(|text| text.trim_end().to_owned())
+            .collect::<Vec<_>>();
+        let status = output.status;
+        if !hook_output.is_empty() && (verbose || !status.success()) {
+            options.report(format_args!("{}", hook_output.join("\n")));
+        }
+        if !status.success() {
+            if verbose {
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) failed hook `{hook}` ({status})",
+                    style::fail()
+                ));
+            }
+            return Ok(Err(PackageValidationErrorKind::HookFailed {
+                hook: hook.clone(),
+                exit_code: status.code(),
+            }));
+        }
+    }
+    Ok(Ok(()))
+}
@@ -0,0 +1,147 @@
+//! The report written with `--format json`, which other tools can deserialize.
+
+use cargo_metadata::{camino::Utf8PathBuf, PackageId};
+use serde::{Deserialize, Serialize};
+
+use crate::{PackageStatus, PackageValidationErrorKind, WarningScope, WorkspaceReport};
+
+/// The results of checking a workspace, in a form which can be written as JSON and read back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    /// Every package which was considered, whether or not it was checked.
+    pub packages: Vec<Package>,
+    /// Every package which failed the check.
+    pub failing_packages: Vec<Failure>,
+    /// Every warning from advisory checks, about the workspace or any package.
+    pub warnings: Vec<Warning>,
+}
+
+impl Report {
+    /// Build the report for the results of a check.
+    #[must_use]
+    pub fn new(report: &WorkspaceReport) -> Self {
+        let packages = report
+            .packages
+            .iter()
+            .map(|result| {
+                let result = &result.result;
+                let (status, failure) = match &result.status {
+                    PackageStatus::Passed => (Status::Passed, None),
+                    PackageStatus::Failed(kind) => (Status::Failed, Some(FailureKind::new(kind))),
+                    PackageStatus::Skipped(reason) => (Status::Skipped(reason.to_string()), None),
+                };
+                Package {
+                    name: result.name.clone(),
+                    package: result.package.clone(),
+                    manifest_path: result.manifest_path.clone(),
+                    status,
+                    failure,
+                }
+            })
+            .collect::<Vec<_>>();
+        let failing_packages = packages
+            .iter()
+            .filter_map(|package| {
+                Some(Failure {
+                    package: package.package.clone(),
+                    manifest_path: package.manifest_path.clone(),
+                    kind: package.failure.clone()?,
+                })
+            })
+            .collect();
+        let warnings = report
+            .warnings
+            .iter()
+            .chain(
+                report
+                    .packages
+                    .iter()
+                    .flat_map(|result| &result.result.warnings),
+            )
+            .map(|warning| Warning {
+                scope: warning.scope.clone(),
+                code: warning.code.clone(),
+                message: warning.message.clone(),
+            })
+            .collect();
+        Self {
+            packages,
+            failing_packages,
+            warnings,
+        }
+    }
+}
+
+/// The outcome for a single package in a [`Report`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+    /// Which package this is.
+    pub package: PackageId,
+    /// The path to the package's manifest.
+    pub manifest_path: Utf8PathBuf,
+    /// Whether the package passed the check.
+    pub status: Status,
+    /// Why the package failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<FailureKind>,
+}
+
+/// Whether a package passed the check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The package passed.
+    Passed,
+    /// The package failed, for the reason in [`Package::failure`].
+    Failed,
+    /// The package wasn't checked, for the given reason.
+    Skipped(String),
+}
+
+/// A failing package in a [`Report`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Failure {
+    /// Which package failed.
+    pub package: PackageId,
+    /// The path to the package's manifest.
+    pub manifest_path: Utf8PathBuf,
+    /// Why the package failed.
+    #[serde(flatten)]
+    pub kind: FailureKind,
+}
+
+/// Why a package failed, as serialized from a [`PackageValidationErrorKind`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureKind {
+    /// The kind of failure, such as `missing` or `wrong_value`.
+    pub kind: String,
+    /// Any details of the failure, such as the value found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+impl FailureKind {
+    /// Convert a failure kind to the form it takes in a [`Report`].
+    ///
+    /// # Panics
+    /// Never, since every failure kind serializes to an object with a `kind` tag.
+    #[must_use]
+    pub fn new(kind: &PackageValidationErrorKind) -> Self {
+        serde_json::to_value(kind)
+            .and_then(serde_json::from_value)
+            .expect("Failure kinds serialize to a tagged JSON object")
+    }
+}
+
+/// A warning from an advisory check, in a [`Report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    /// What the warning is about.
+    pub scope: WarningScope,
+    /// A short, stable identifier for the kind of warning, such as `priority-override`.
+    pub code: String,
+    /// A description of the problem.
+    pub message: String,
+}
@@ -1,48 +1,1613 @@
 //! Parse a cargo workspace and check that all packages have `lints.workspace=true` set.
 
-use cargo_metadata::{MetadataCommand, PackageId};
-use std::{collections::HashSet, fs};
-use std::{error, fmt, io};
+use cargo_metadata::{
+    camino::{Utf8Path, Utf8PathBuf},
+    Metadata, MetadataCommand, PackageId,
+};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs, iter,
+};
+use std::{
+    error, fmt, io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+mod advisory;
+mod autofix;
+mod baseline;
+mod cargo_config;
+mod config;
+mod custom;
+mod deps;
+mod effective;
+mod filter;
+pub mod format;
+mod git;
+mod hook;
+pub mod json;
+mod migrate;
+mod path;
+mod policy;
+mod progress;
+mod root;
+mod severity;
+pub mod style;
+
+pub use advisory::{SourceFiles, Warning, WarningScope};
+pub use autofix::{apply_fix, fixed_manifest, plan_fixes, FalseFix, FixEdit, PlannedFix};
+pub use baseline::{Baseline, KnownFailure};
+pub use config::{Config, LintPolicy, CONFIG_FILE_NAME};
+pub use custom::{LintsCheck, NamedCheck, WorkspaceCheck};
+pub use effective::{package_lints, PackageLints, ResolvedLint};
+pub use filter::{ExcludePattern, LocalLintPattern};
+pub use git::changed_manifests;
+pub use migrate::{apply_migration, plan_migration, LintConflict, Migration};
+pub use policy::{LintLevel, RequiredLint};
+pub use progress::{Reporter, Stage, StderrReporter};
+pub use root::{find_workspace_roots, read_workspace_lints};
+pub use severity::Severity;
+
+/// Options controlling which packages are checked.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ValidationOptions {
+    /// Packages matching any of these patterns are skipped.
+    ///
+    /// When checking a whole workspace, the patterns listed in
+    /// `workspace.metadata.workspace-lints.exclude` in its root manifest are also used.
+    pub excludes: Vec<ExcludePattern>,
+    /// External commands to run for each package which passes the built-in check.
+    ///
+    /// A package fails if any of these exits unsuccessfully. Each hook is split on whitespace
+    /// into a program and its arguments, and the path to the package's manifest is passed as an
+    /// additional final argument. The package name, manifest path, and (if known) workspace root
+    /// are also provided in the `CARGO_WORKSPACE_LINTS_PACKAGE_NAME`,
+    /// `CARGO_WORKSPACE_LINTS_MANIFEST_PATH`, and `CARGO_WORKSPACE_LINTS_WORKSPACE_ROOT`
+    /// environment variables.
+    pub hooks: Vec<String>,
+    /// Report packages which define their own lint tables instead of inheriting the workspace
+    /// lints as [`PackageValidationErrorKind::InlineLints`], instead of
+    /// [`PackageValidationErrorKind::WorkspaceLintsMissing`].
+    pub detect_inline_lints: bool,
+    /// Warn about packages whose own `[lints]` tables set a `priority` for some lint.
+    pub check_priority_overrides: bool,
+    /// Warn about packages which inherit the workspace lints but allow a broad lint group (such
+    /// as `warnings`) in their own `[lints]` tables.
+    pub check_negating_overrides: bool,
+    /// Warn about lints in the tool tables of the root `[workspace.lints]` which aren't in
+    /// alphabetical order.
+    ///
+    /// This only applies when the workspace root is known.
+    pub check_root_lints_sorted: bool,
+    /// Warn about lints in the root `[workspace.lints]` which a lint group in the same table may
+    /// override, because the group sets a different level without a lower `priority`.
+    ///
+    /// This only applies when the workspace root is known. The members of each group are listed
+    /// by running `rustc`, `clippy-driver`, and `rustdoc` from the `PATH`, as for
+    /// [`Self::check_lint_names`].
+    pub check_root_lint_priorities: bool,
+    /// Warn about lints in the root `[workspace.lints]` which have been renamed or removed, or
+    /// which are set more than once under different names, such as an old name in one tool table
+    /// and the new name in another.
+    ///
+    /// This only applies when the workspace root is known.
+    pub check_root_lint_consistency: bool,
+    /// Warn about flags in the `rustflags` of the workspace's `.cargo/config.toml` files which
+    /// override the levels the root `[workspace.lints]` sets, such as `-A clippy::all`.
+    ///
+    /// This only applies when the workspace root is known. The lints in groups set by the flags
+    /// are listed by running `rustc`, `clippy-driver`, and `rustdoc` from the `PATH`, as for
+    /// [`Self::check_lint_names`].
+    pub check_rustflags: bool,
+    /// Warn about packages under the workspace root directory which escape the checks, because
+    /// they aren't members (such as those listed in `workspace.exclude`) or are in nested
+    /// workspaces.
+    ///
+    /// This only applies when the workspace root is known.
+    pub check_escaped_packages: bool,
+    /// Values of `lints.workspace` other than `true` which are accepted as passing.
+    ///
+    /// This lets packages record a deliberate opt-out (such as `lints.workspace = false`) while
+    /// still requiring the field to be present.
+    pub allowed_values: Vec<toml::Value>,
+    /// Dotted paths to keys which must be `true` in every package's manifest, such as
+    /// `package.edition.workspace`.
+    ///
+    /// If this is empty, only `lints.workspace` is required. The other options about the value of
+    /// `lints.workspace` only apply if it is required.
+    pub required_keys: Vec<String>,
+    /// Check packages which can't be published (with `publish = false` or `publish = []`).
+    ///
+    /// These are skipped by default.
+    pub include_unpublished: bool,
+    /// Fail packages which set `lints.workspace = true` but also define their own lints in other
+    /// `[lints]` tables, as [`PackageValidationErrorKind::LocalLintsPresent`].
+    pub reject_local_lints: bool,
+    /// Local lints which are accepted even when [`Self::reject_local_lints`] is set.
+    ///
+    /// A tool table only counts against the package if it sets some lint not matching any of
+    /// these.
+    pub allowed_local_lints: Vec<LocalLintPattern>,
+    /// Fail with [`WorkspaceValidationError::WorkspaceLintsUndefined`] if the workspace root
+    /// doesn't define any `[workspace.lints]` for the packages to inherit.
+    ///
+    /// A `[workspace.lints]` table which is empty, or has only empty tool tables, is treated the
+    /// same as a missing one.
+    pub require_root_lints: bool,
+    /// Check a project which is a single package rather than a workspace (with no `[workspace]`
+    /// table in its manifest) for defining its own lints, failing it with
+    /// [`PackageValidationErrorKind::PackageLintsMissing`] if it has none.
+    ///
+    /// Otherwise, checking such a project fails with
+    /// [`WorkspaceValidationError::NotAWorkspace`], since it has no `[workspace.lints]` to
+    /// inherit. The checks on the root `[workspace.lints]` don't apply either way.
+    pub allow_single_package: bool,
+    /// Skip workspace members whose manifests aren't inside the workspace root directory, such as
+    /// path dependencies in a sibling checkout.
+    ///
+    /// This only applies when the workspace root is known.
+    pub workspace_root_only: bool,
+    /// Fields of `[package]`, such as `edition` or `license`, which every package must inherit
+    /// from `[workspace.package]` with `field.workspace = true`.
+    ///
+    /// These are checked in addition to the required keys.
+    pub inherited_fields: Vec<String>,
+    /// Warn about members whose sources turn off lints configured in the root
+    /// `[workspace.lints]` with crate-level `#![allow(...)]` or `#![expect(...)]` attributes,
+    /// scanning the given files.
+    ///
+    /// This only applies when the workspace root is known.
+    pub check_source_allows: Option<SourceFiles>,
+    /// Lints which the root `[workspace.lints]` must set to at least the given level, failing
+    /// with [`WorkspaceValidationError::RootLintsPolicy`] otherwise.
+    ///
+    /// Only lints set by name count, not those set through a group which contains them.
+    pub required_lints: Vec<RequiredLint>,
+    /// Fail with [`WorkspaceValidationError::RootLintsPolicy`] if the root `[workspace.lints]`
+    /// sets any lint to `allow`, other than those in [`Self::permitted_root_allows`].
+    pub reject_root_allows: bool,
+    /// Globs over the lints (such as `clippy::module_name_repetitions`) which the root
+    /// `[workspace.lints]` may set to `allow` even when [`Self::reject_root_allows`] is set.
+    pub permitted_root_allows: Vec<String>,
+    /// Fail with [`WorkspaceValidationError::RootLintsPolicy`] if the root `[workspace.lints]`
+    /// sets any lint which isn't a known lint or lint group of its tool, such as a misspelling.
+    ///
+    /// The known lints are listed by running `rustc`, `clippy-driver`, and `rustdoc` from the
+    /// `PATH`, as needed, so failing to run them fails with [`WorkspaceValidationError::Io`].
+    pub check_lint_names: bool,
+    /// Custom checks which every package must also pass, run in order after the built-in
+    /// [`LintsCheck`] passes, and before any hooks.
+    ///
+    /// These only apply when checking a workspace with `cargo metadata`, since they need the
+    /// package details from it.
+    pub checks: Vec<Arc<dyn WorkspaceCheck>>,
+    /// Optional built-in checks which every package must also pass, run before
+    /// [`Self::checks`].
+    ///
+    /// Like the custom checks, these only apply when checking a workspace with `cargo metadata`.
+    pub named_checks: Vec<NamedCheck>,
+    /// How seriously to take the problems found by each check, by name.
+    ///
+    /// The built-in check is named `lints`, hooks are named `hooks`, other checks are named as in
+    /// [`WorkspaceCheck::name`], and advisory checks are named by the [`Warning::code`]s they
+    /// produce. Checks which fail packages default to [`Severity::Deny`], and advisory checks to
+    /// [`Severity::Warn`]. Packages whose manifests can't be read or parsed always fail.
+    pub severities: BTreeMap<String, Severity>,
+    /// Treat every check at [`Severity::Warn`] as [`Severity::Deny`].
+    pub deny_warnings: bool,
+    /// If set, only check the packages with these manifests (such as those found by
+    /// [`changed_manifests`]), skipping the rest with [`SkipReason::Unchanged`].
+    ///
+    /// The checks on the root `[workspace.lints]` are likewise only run if the workspace root
+    /// manifest is listed.
+    pub changed_manifests: Option<Vec<Utf8PathBuf>>,
+    /// Report the progress of the check on each package (such as `PASS:` and `FAIL:` lines) to
+    /// [`Self::reporter`].
+    ///
+    /// The functions which also take a `verbose` argument report progress if either is set.
+    pub verbose: bool,
+    /// Where progress is reported when checking verbosely, or stderr if unset.
+    ///
+    /// The reporter is also told of each [`Stage`] of the check, verbose or not.
+    pub reporter: Option<Arc<dyn Reporter>>,
+}
+
+impl ValidationOptions {
+    /// Start building options, from the defaults.
+    pub fn builder() -> ValidationOptionsBuilder {
+        ValidationOptionsBuilder::default()
+    }
+
+    /// Report a line of progress to [`Self::reporter`].
+    ///
+    /// While [`Self::check_each`] is checking an item on this thread, the line is held back until
+    /// the lines of the items before it have been reported.
+    fn report(&self, line: fmt::Arguments<'_>) {
+        let line = line.to_string();
+        let line = BUFFERED_LINES.with(|buffered| match &mut *buffered.borrow_mut() {
+            Some(lines) => {
+                lines.push(line);
+                None
+            }
+            None => Some(line),
+        });
+        let Some(line) = line else {
+            return;
+        };
+        match &self.reporter {
+            Some(reporter) => reporter.report(&line),
+            None => StderrReporter.report(&line),
+        }
+    }
+
+    /// Tell [`Self::reporter`] that the check has reached the given stage.
+    fn stage(&self, stage: Stage) {
+        if let Some(reporter) = &self.reporter {
+            reporter.stage(stage);
+        }
+    }
+
+    /// Check each of `items` in parallel with `check`, telling [`Self::reporter`] how many are
+    /// done.
+    ///
+    /// The results are collected in the same order regardless of which finish first. In verbose
+    /// mode, the lines reported while checking each item are held back and passed to
+    /// [`Self::reporter`] from this thread in the order of the items, so that they don't
+    /// interleave.
+    fn check_each<T: Sync, R: Send>(
+        &self,
+        items: &[T],
+        verbose: bool,
+        check: impl Fn(&T) -> R + Sync,
+    ) -> Vec<R> {
+        let total = items.len();
+        let checked = AtomicUsize::new(0);
+        self.stage(Stage::Packages { checked: 0, total });
+        let check = |item: &T| {
+            let result = check(item);
+            let checked = checked.fetch_add(1, Ordering::Relaxed) + 1;
+            self.stage(Stage::Packages { checked, total });
+            result
+        };
+        if !verbose {
+            return items.par_iter().map(check).collect();
+        }
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                items
+                    .par_iter()
+                    .enumerate()
+                    .for_each_with(sender, |sender, (index, item)| {
+                        BUFFERED_LINES.with(|buffered| *buffered.borrow_mut() = Some(Vec::new()));
+                        let result = check(item);
+                        let lines = BUFFERED_LINES.with(|buffered| buffered.borrow_mut().take());
+                        // The receiver only hangs up if this thread panicked, so there's no one
+                        // left to report to.
+                        let _ = sender.send((index, result, lines.unwrap_or_default()));
+                    });
+            });
+            // Items which finished before some item ahead of them, waiting to be reported.
+            let mut pending = BTreeMap::new();
+            let mut results = Vec::with_capacity(total);
+            for (index, result, lines) in receiver {
+                pending.insert(index, (result, lines));
+                while let Some((result, lines)) = pending.remove(&results.len()) {
+                    for line in lines {
+                        self.report(format_args!("{line}"));
+                    }
+                    results.push(result);
+                }
+            }
+            results
+        })
+    }
+
+    /// Whether the checks on the root `[workspace.lints]` should be run, given
+    /// [`Self::changed_manifests`].
+    fn checks_root(&self, workspace_root: &Utf8Path) -> bool {
+        self.changed_manifests.as_ref().map_or(true, |changed| {
+            changed.contains(&workspace_root.join("Cargo.toml"))
+        })
+    }
+}
+
+thread_local! {
+    /// The lines reported by the item being checked on this thread, while
+    /// [`ValidationOptions::check_each`] is holding them back to report them in order.
+    static BUFFERED_LINES: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Builds [`ValidationOptions`] step by step, starting from the defaults.
+///
+/// Options without a method here can be set on the built options directly.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct ValidationOptionsBuilder {
+    options: ValidationOptions,
+}
+
+impl ValidationOptionsBuilder {
+    /// Set [`ValidationOptions::verbose`].
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.options.verbose = verbose;
+        self
+    }
+
+    /// Set [`ValidationOptions::reporter`].
+    pub fn reporter(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.options.reporter = Some(Arc::new(reporter));
+        self
+    }
+
+    /// Add to [`ValidationOptions::excludes`].
+    pub fn exclude(mut self, pattern: ExcludePattern) -> Self {
+        self.options.excludes.push(pattern);
+        self
+    }
+
+    /// Add to [`ValidationOptions::hooks`].
+    pub fn hook(mut self, hook: impl Into<String>) -> Self {
+        self.options.hooks.push(hook.into());
+        self
+    }
+
+    /// Add to [`ValidationOptions::required_keys`].
+    pub fn require_key(mut self, key: impl Into<String>) -> Self {
+        self.options.required_keys.push(key.into());
+        self
+    }
+
+    /// Add to [`ValidationOptions::inherited_fields`].
+    pub fn inherit_field(mut self, field: impl Into<String>) -> Self {
+        self.options.inherited_fields.push(field.into());
+        self
+    }
+
+    /// Set [`ValidationOptions::include_unpublished`].
+    pub fn include_unpublished(mut self, include_unpublished: bool) -> Self {
+        self.options.include_unpublished = include_unpublished;
+        self
+    }
+
+    /// Add to [`ValidationOptions::required_lints`].
+    pub fn require_lint(mut self, lint: RequiredLint) -> Self {
+        self.options.required_lints.push(lint);
+        self
+    }
+
+    /// Add to [`ValidationOptions::named_checks`].
+    pub fn named_check(mut self, check: NamedCheck) -> Self {
+        self.options.named_checks.push(check);
+        self
+    }
+
+    /// Add to [`ValidationOptions::checks`].
+    pub fn check(mut self, check: impl WorkspaceCheck + 'static) -> Self {
+        self.options.checks.push(Arc::new(check));
+        self
+    }
+
+    /// Set the severity of a check in [`ValidationOptions::severities`].
+    pub fn severity(mut self, check: impl Into<String>, severity: Severity) -> Self {
+        self.options.severities.insert(check.into(), severity);
+        self
+    }
+
+    /// Set [`ValidationOptions::deny_warnings`].
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.options.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// Finish building the options.
+    #[must_use]
+    pub fn build(self) -> ValidationOptions {
+        self.options
+    }
+}
 
 /// Validate that all packages in the workspace have `lints.workspace = true`.
 ///
+/// Progress is reported to [`ValidationOptions::reporter`] if [`ValidationOptions::verbose`] is
+/// set.
+///
 /// # Arguments
 /// * `metadata_command`: The command to run to generate metadata.
-/// * `verbose`: If set to true, provides more detailed output to stderr.
+/// * `options`: Options controlling which packages are checked.
 ///
 /// # Errors
 /// If the validation fails, it returns an error indicating the kind of failure. The failure may
 /// indicate I/O-related failures to read and parse data, or it may indicate that individual
 /// packages do not have `lints.workspace = true`.
+pub fn validate(
+    metadata_command: &MetadataCommand,
+    options: &ValidationOptions,
+) -> Result<(), WorkspaceValidationError> {
+    validate_results(check_workspace(metadata_command, options, false)?)
+}
+
+/// Validate that all packages in the workspace have `lints.workspace = true`.
+///
+/// # Arguments
+/// * `metadata_command`: The command to run to generate metadata.
+/// * `verbose`: If set to true, provides more detailed output to stderr.
+///
+/// # Errors
+/// As with [`validate`].
+#[deprecated(note = "use `validate`, with `ValidationOptions::verbose` instead of `verbose`")]
 pub fn validate_workspace(
     metadata_command: &MetadataCommand,
     verbose: bool,
 ) -> Result<(), WorkspaceValidationError> {
-    let metadata = metadata_command.exec()?;
-    let workspace_members = metadata
-        .workspace_members
+    validate(
+        metadata_command,
+        &ValidationOptions {
+            verbose,
+            ..ValidationOptions::default()
+        },
+    )
+}
+
+/// Validate that all the given package manifests have `lints.workspace = true`, without access to
+/// the workspace root.
+///
+/// See [`check_member_manifests`] for details.
+///
+/// # Arguments
+/// * `manifest_paths`: The paths to the `Cargo.toml` manifests of the members to check.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// If the validation fails, it returns an error indicating the kind of failure, as with
+/// [`validate`].
+pub fn validate_member_manifests(
+    manifest_paths: &[Utf8PathBuf],
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), WorkspaceValidationError> {
+    validate_results(check_member_manifests(manifest_paths, options, verbose)?)
+}
+
+/// Convert the per-package results of a check into an error if any package failed.
+///
+/// # Errors
+/// Returns [`WorkspaceValidationError::FailingPackages`] listing every failing package, if there
+/// are any.
+pub fn validate_results(results: Vec<PackageResult>) -> Result<(), WorkspaceValidationError> {
+    let failing_packages = failing_packages(results);
+    if failing_packages.is_empty() {
+        Ok(())
+    } else {
+        Err(WorkspaceValidationError::FailingPackages(failing_packages))
+    }
+}
+
+/// Pick out the packages which failed from the per-package results of a check.
+#[must_use]
+pub fn failing_packages(results: Vec<PackageResult>) -> Vec<PackageValidationError> {
+    results
         .into_iter()
-        .collect::<HashSet<_>>();
-    let mut failing_packages = Vec::new();
-    for package in metadata.packages {
-        // Skip anything not in the workspace
-        if !workspace_members.contains(&package.id) {
-            continue;
-        }
-        let manifest_path = package.manifest_path.as_path();
-        let manifest: toml::Table = toml::from_str(&fs::read_to_string(manifest_path)?)?;
-        if let Err(kind) = validate_package(&package, &manifest, verbose) {
-            failing_packages.push(PackageValidationError {
+        .filter_map(|result| match result.status {
+            PackageStatus::Failed(kind) => Some(PackageValidationError {
                 kind,
-                package: package.id,
-            });
+                package: result.package,
+                manifest_path: result.manifest_path,
+            }),
+            PackageStatus::Passed | PackageStatus::Skipped(_) => None,
+        })
+        .collect()
+}
+
+/// Check all packages in the workspace, returning the packages which fail.
+///
+/// This is like [`validate`], but returns the failures as data (with an empty list
+/// meaning every package passed) instead of as an error.
+///
+/// # Arguments
+/// * `metadata_command`: The command to run to generate metadata.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error if the workspace couldn't be checked at all, as with [`check_workspace`].
+pub fn check_workspace_failures(
+    metadata_command: &MetadataCommand,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageValidationError>, WorkspaceValidationError> {
+    Ok(failing_packages(check_workspace(
+        metadata_command,
+        options,
+        verbose,
+    )?))
+}
+
+/// Check all packages in the workspace, returning the outcome for each checked package.
+///
+/// Unlike [`validate`], passing packages are included in the output, and failing
+/// packages are not reported as an error.
+///
+/// # Arguments
+/// * `metadata_command`: The command to run to generate metadata.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error if metadata couldn't be generated, the workspace root couldn't be read or
+/// doesn't define any `[workspace.lints]` (when [`ValidationOptions::require_root_lints`] is set),
+/// or a hook couldn't be run. Packages failing the check
+/// (including those whose manifests can't be read or parsed) are not errors, and are reported in
+/// the returned results instead.
+pub fn check_workspace(
+    metadata_command: &MetadataCommand,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageResult>, WorkspaceValidationError> {
+    options.stage(Stage::Metadata);
+    check_metadata(&metadata_command.exec()?, options, verbose)
+}
+
+/// Check the workspace under several configurations (such as different feature sets), merging
+/// the results.
+///
+/// Each configuration is a label (for reporting) along with the command to generate metadata in
+/// that configuration. Packages surfaced by several configurations are only checked once, and the
+/// result records every configuration which surfaced the package.
+///
+/// Cargo lists every workspace member whatever the features or platform, so a configuration can
+/// only surface other packages through its dependencies. If a command is run without
+/// [`MetadataCommand::no_deps`], the packages checked also include the path dependencies outside
+/// the workspace which are resolved in that configuration, such as one behind an optional
+/// feature.
+///
+/// # Arguments
+/// * `configurations`: The labels and commands for each configuration.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error if metadata couldn't be generated for some configuration, or as with
+/// [`check_workspace`].
+pub fn check_workspace_configurations(
+    configurations: &[(String, MetadataCommand)],
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<WorkspaceReport, WorkspaceValidationError> {
+    let metadata = configurations
+        .iter()
+        .map(|(label, metadata_command)| {
+            options.stage(Stage::Metadata);
+            Ok((label.clone(), metadata_command.exec()?))
+        })
+        .collect::<Result<Vec<_>, WorkspaceValidationError>>()?;
+    check_metadata_configurations(metadata, options, verbose)
+}
+
+/// The path dependencies outside the workspace which were resolved in the metadata, if it was
+/// generated with its dependencies.
+fn resolved_path_dependencies(metadata: &Metadata) -> Vec<PackageId> {
+    let Some(resolve) = &metadata.resolve else {
+        return Vec::new();
+    };
+    let members = metadata.workspace_members.iter().collect::<HashSet<_>>();
+    let local = metadata
+        .packages
+        .iter()
+        .filter(|package| package.source.is_none())
+        .map(|package| &package.id)
+        .collect::<HashSet<_>>();
+    resolve
+        .nodes
+        .iter()
+        .map(|node| &node.id)
+        .filter(|id| local.contains(id) && !members.contains(id))
+        .cloned()
+        .collect()
+}
+
+/// Check the workspace under several configurations, as with [`check_workspace_configurations`],
+/// but with metadata which has already been generated for each.
+///
+/// # Arguments
+/// * `configurations`: The labels and metadata for each configuration.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error as with [`check_metadata`].
+pub fn check_metadata_configurations(
+    configurations: Vec<(String, Metadata)>,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<WorkspaceReport, WorkspaceValidationError> {
+    let verbose = verbose || options.verbose;
+    let Some(workspace_root) = configurations
+        .first()
+        .map(|(_, metadata)| metadata.workspace_root.clone())
+    else {
+        return Ok(WorkspaceReport {
+            workspace_root: None,
+            packages: Vec::new(),
+            warnings: Vec::new(),
+        });
+    };
+    // Every configuration shares the workspace root, so it's only checked once.
+    let root = check_root(&workspace_root, options, verbose)?;
+    let mut results = Vec::<ConfiguredPackageResult>::new();
+    for (label, mut metadata) in configurations {
+        metadata
+            .workspace_members
+            .extend(resolved_path_dependencies(&metadata));
+        // Don't check packages again if an earlier configuration already surfaced them.
+        metadata.workspace_members.retain(|id| {
+            match results
+                .iter_mut()
+                .find(|result| result.result.package == *id)
+            {
+                Some(result) => {
+                    result.configurations.push(label.clone());
+                    false
+                }
+                None => true,
+            }
+        });
+        results.extend(
+            check_members(&metadata, &root, options, verbose)?
+                .into_iter()
+                .map(|result| ConfiguredPackageResult {
+                    result,
+                    configurations: vec![label.clone()],
+                }),
+        );
+    }
+    let mut warnings = if options.checks_root(&workspace_root) {
+        workspace_warnings(&workspace_root, &results, options)?
+    } else {
+        Vec::new()
+    };
+    let problems = severity::apply_to_workspace(&mut warnings, options);
+    if !problems.is_empty() {
+        return Err(WorkspaceValidationError::RootLintsPolicy {
+            path: workspace_root.join("Cargo.toml"),
+            problems,
+        });
+    }
+    if verbose {
+        for warning in &warnings {
+            options.report(format_args!("WARN: Workspace: {warning}"));
         }
     }
-    if failing_packages.is_empty() {
-        Ok(())
+    Ok(WorkspaceReport {
+        workspace_root: Some(workspace_root),
+        packages: results,
+        warnings,
+    })
+}
+
+/// The advisory warnings about the workspace as a whole, from the checks turned on in `options`.
+fn workspace_warnings(
+    workspace_root: &Utf8Path,
+    results: &[ConfiguredPackageResult],
+    options: &ValidationOptions,
+) -> Result<Vec<Warning>, WorkspaceValidationError> {
+    let mut warnings = Vec::new();
+    if options.check_root_lints_sorted {
+        warnings.extend(
+            root::unsorted_workspace_lints(workspace_root)?
+                .into_iter()
+                .map(|message| Warning {
+                    scope: WarningScope::Workspace,
+                    code: "unsorted-root-lints".to_owned(),
+                    message,
+                }),
+        );
+    }
+    if options.check_root_lint_priorities {
+        let root_lints = root::read_workspace_lints(workspace_root)?.unwrap_or_default();
+        warnings.extend(
+            policy::shadowed_root_lints(&root_lints)?
+                .into_iter()
+                .map(|message| Warning {
+                    scope: WarningScope::Workspace,
+                    code: "shadowed-lint".to_owned(),
+                    message,
+                }),
+        );
+    }
+    if options.check_root_lint_consistency {
+        let root_lints = root::read_workspace_lints(workspace_root)?.unwrap_or_default();
+        warnings.extend(
+            policy::inconsistent_root_lints(&root_lints)
+                .into_iter()
+                .map(|message| Warning {
+                    scope: WarningScope::Workspace,
+                    code: "inconsistent-lint".to_owned(),
+                    message,
+                }),
+        );
+    }
+    if options.check_rustflags {
+        let root_lints = root::read_workspace_lints(workspace_root)?.unwrap_or_default();
+        warnings.extend(
+            cargo_config::rustflag_conflicts(workspace_root, &root_lints)?
+                .into_iter()
+                .map(|message| Warning {
+                    scope: WarningScope::Workspace,
+                    code: "rustflags-override".to_owned(),
+                    message,
+                }),
+        );
+    }
+    if options.check_escaped_packages {
+        let member_manifests = results
+            .iter()
+            .map(|result| result.result.manifest_path.as_path())
+            .collect::<Vec<_>>();
+        warnings.extend(
+            root::escaped_packages(workspace_root, &member_manifests)?
+                .into_iter()
+                .map(|message| Warning {
+                    scope: WarningScope::Workspace,
+                    code: "escaped-package".to_owned(),
+                    message,
+                }),
+        );
+    }
+    Ok(warnings)
+}
+
+/// Check all packages in already-generated workspace metadata.
+///
+/// This is the same as [`check_workspace`], but takes metadata which has already been generated
+/// (e.g. with [`MetadataCommand::parse`]) instead of running `cargo metadata`.
+///
+/// # Arguments
+/// * `metadata`: The metadata for the workspace.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error if [`ValidationOptions::require_root_lints`] is set and the workspace root
+/// couldn't be read or doesn't define any `[workspace.lints]` for the packages to inherit, or if a
+/// hook couldn't be run. Packages whose manifests can't be read or parsed fail the check instead.
+pub fn check_metadata(
+    metadata: &Metadata,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageResult>, WorkspaceValidationError> {
+    let verbose = verbose || options.verbose;
+    let root = check_root(&metadata.workspace_root, options, verbose)?;
+    check_members(metadata, &root, options, verbose)
+}
+
+/// What was found checking a workspace root, as needed to then check its members.
+enum CheckedRoot {
+    /// The root manifest is a single package rather than a workspace.
+    SingleProject,
+    /// The root manifest is a workspace.
+    Workspace {
+        /// Its `[workspace.lints]`, if the options need them.
+        lints: Option<toml::Table>,
+        /// The optional built-in checks named in the options.
+        named_checks: Vec<Arc<dyn WorkspaceCheck>>,
+    },
+}
+
+/// Check the root manifest of the workspace against the policies in `options`.
+///
+/// This is done once for a workspace, however many configurations it's checked under.
+///
+/// # Errors
+/// Returns an error as with [`check_metadata`], other than for a problem with a member.
+fn check_root(
+    workspace_root: &Utf8Path,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<CheckedRoot, WorkspaceValidationError> {
+    if !root::is_workspace(workspace_root)? {
+        return Ok(CheckedRoot::SingleProject);
+    }
+    let checks_root = options.checks_root(workspace_root);
+    let workspace_lints = if options.require_root_lints
+        || options.check_source_allows.is_some()
+        || !options.required_lints.is_empty()
+        || options.reject_root_allows
+        || options.check_lint_names
+    {
+        root::read_workspace_lints(workspace_root)?
     } else {
-        Err(WorkspaceValidationError::FailingPackages(failing_packages))
+        None
+    };
+    // `lints.workspace = true` does nothing unless the workspace root has lints to inherit.
+    if checks_root
+        && options.require_root_lints
+        && !workspace_lints.as_ref().is_some_and(|lints| {
+            lints
+                .values()
+                .any(|tool| tool.as_table().map_or(true, |tool| !tool.is_empty()))
+        })
+    {
+        return Err(WorkspaceValidationError::WorkspaceLintsUndefined(
+            workspace_root.join("Cargo.toml"),
+        ));
+    }
+    let no_lints = toml::Table::new();
+    let root_lints = workspace_lints.as_ref().unwrap_or(&no_lints);
+    let mut problems = Vec::new();
+    if checks_root {
+        problems.extend(policy::weak_required_lints(
+            root_lints,
+            &options.required_lints,
+        ));
+    }
+    if checks_root && options.reject_root_allows {
+        problems.extend(policy::allowed_root_lints(
+            root_lints,
+            &options.permitted_root_allows,
+        ));
+    }
+    if checks_root && options.check_lint_names {
+        problems.extend(policy::unknown_root_lints(root_lints)?);
+    }
+    if !problems.is_empty() {
+        if verbose {
+            for problem in &problems {
+                options.report(format_args!("{} Workspace: {problem}", style::fail()));
+            }
+        }
+        return Err(WorkspaceValidationError::RootLintsPolicy {
+            path: workspace_root.join("Cargo.toml"),
+            problems,
+        });
+    }
+    let named_checks = options
+        .named_checks
+        .iter()
+        .map(|check| named_check(*check, workspace_root))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CheckedRoot::Workspace {
+        lints: workspace_lints,
+        named_checks,
+    })
+}
+
+/// Check the members in `metadata`, once its workspace root has been checked.
+fn check_members(
+    metadata: &Metadata,
+    root: &CheckedRoot,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageResult>, WorkspaceValidationError> {
+    let CheckedRoot::Workspace {
+        lints: workspace_lints,
+        named_checks,
+    } = root
+    else {
+        return check_single_project(metadata, options, verbose);
+    };
+    let workspace_excludes = workspace_metadata_excludes(metadata);
+    let extended_options;
+    let options = if workspace_excludes.is_empty() && named_checks.is_empty() {
+        options
+    } else {
+        extended_options = ValidationOptions {
+            excludes: options
+                .excludes
+                .iter()
+                .cloned()
+                .chain(workspace_excludes)
+                .collect(),
+            checks: named_checks
+                .iter()
+                .chain(&options.checks)
+                .cloned()
+                .collect(),
+            ..options.clone()
+        };
+        &extended_options
+    };
+    let workspace_members = metadata.workspace_members.iter().collect::<HashSet<_>>();
+    // Skip anything not in the workspace
+    let packages = metadata
+        .packages
+        .iter()
+        .filter(|package| workspace_members.contains(&package.id))
+        .collect::<Vec<_>>();
+    options
+        .check_each(&packages, verbose, |package| {
+            check_package(
+                package,
+                &metadata.workspace_root,
+                workspace_lints.as_ref(),
+                options,
+                verbose,
+            )
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Set up one of the optional built-in checks for the workspace with the given root.
+fn named_check(
+    check: NamedCheck,
+    workspace_root: &Utf8Path,
+) -> Result<Arc<dyn WorkspaceCheck>, WorkspaceValidationError> {
+    Ok(match check {
+        NamedCheck::WorkspaceDeps => Arc::new(deps::WorkspaceDependencies {
+            names: root::read_workspace_dependencies(workspace_root)?,
+        }),
+    })
+}
+
+/// The exclude patterns listed in `workspace.metadata.workspace-lints.exclude` in the root
+/// manifest.
+fn workspace_metadata_excludes(metadata: &Metadata) -> Vec<ExcludePattern> {
+    metadata
+        .workspace_metadata
+        .get("workspace-lints")
+        .and_then(|metadata| metadata.get("exclude"))
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .map(ExcludePattern::parse)
+        .collect()
+}
+
+/// Check a single package from the workspace metadata.
+fn check_package(
+    package: &cargo_metadata::Package,
+    workspace_root: &Utf8Path,
+    workspace_lints: Option<&toml::Table>,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<PackageResult, WorkspaceValidationError> {
+    let opt_out = OptOut::from_metadata(&package.metadata);
+    let unpublished = package.publish.as_ref().is_some_and(Vec::is_empty);
+    if let Some(reason) = skip_reason(
+        options,
+        &package.name,
+        &package.manifest_path,
+        workspace_root,
+        manifest_skip_reason(options, &opt_out, unpublished),
+        verbose,
+    ) {
+        return Ok(PackageResult {
+            package: package.id.clone(),
+            name: package.name.clone(),
+            manifest_path: package.manifest_path.clone(),
+            status: PackageStatus::Skipped(reason),
+            warnings: Vec::new(),
+        });
+    }
+    let mut result = match fs::read_to_string(&package.manifest_path) {
+        Ok(contents) => check_manifest(
+            &package.name,
+            package.id.clone(),
+            &package.manifest_path,
+            &contents,
+            options,
+            verbose,
+            |manifest| match run_checks(package, manifest, options, verbose) {
+                Ok(()) => hook::run_hooks(
+                    &package.name,
+                    &package.manifest_path,
+                    workspace_root,
+                    options,
+                    verbose,
+                ),
+                Err(kind) => Ok(Err(kind)),
+            },
+        )?,
+        Err(e) => unreadable_manifest(
+            &package.name,
+            package.id.clone(),
+            &package.manifest_path,
+            e,
+            options,
+            verbose,
+        ),
+    };
+    opt_out.warn_if_unjustified(&mut result, options, verbose);
+    if let (Some(files), Some(workspace_lints)) = (options.check_source_allows, workspace_lints) {
+        // Only packages which inherit the workspace lints can have them overridden.
+        if matches!(result.status, PackageStatus::Passed) {
+            let warnings = source_allow_warnings(package, files, workspace_lints);
+            if verbose {
+                for warning in &warnings {
+                    options.report(format_args!(
+                        "WARN: Package {} ({}): {warning}",
+                        package.name, package.manifest_path
+                    ));
+                }
+            }
+            result.warnings.extend(warnings);
+        }
+    }
+    severity::apply_to_package(&mut result, options);
+    Ok(result)
+}
+
+/// Check a project which isn't a workspace, as for [`check_metadata`].
+///
+/// This fails with [`WorkspaceValidationError::NotAWorkspace`] unless
+/// [`ValidationOptions::allow_single_package`] is set.
+fn check_single_project(
+    metadata: &Metadata,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageResult>, WorkspaceValidationError> {
+    if !options.allow_single_package {
+        return Err(WorkspaceValidationError::NotAWorkspace(
+            metadata.workspace_root.join("Cargo.toml"),
+        ));
+    }
+    let packages = metadata.workspace_packages();
+    Ok(options.check_each(&packages, verbose, |package| {
+        check_single_package(package, &metadata.workspace_root, options, verbose)
+    }))
+}
+
+/// Check the package of a project which isn't a workspace, for
+/// [`ValidationOptions::allow_single_package`].
+///
+/// With no `[workspace.lints]` to inherit, the package must define its own lints in `[lints]`.
+/// The other checks on members, such as the required keys and hooks, aren't run.
+fn check_single_package(
+    package: &cargo_metadata::Package,
+    workspace_root: &Utf8Path,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> PackageResult {
+    let opt_out = OptOut::from_metadata(&package.metadata);
+    let unpublished = package.publish.as_ref().is_some_and(Vec::is_empty);
+    let status = if let Some(reason) = skip_reason(
+        options,
+        &package.name,
+        &package.manifest_path,
+        workspace_root,
+        manifest_skip_reason(options, &opt_out, unpublished),
+        verbose,
+    ) {
+        PackageStatus::Skipped(reason)
+    } else {
+        let (name, manifest_path) = (&package.name, &package.manifest_path);
+        let contents = match fs::read_to_string(manifest_path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return unreadable_manifest(
+                    name,
+                    package.id.clone(),
+                    manifest_path,
+                    e,
+                    options,
+                    verbose,
+                )
+            }
+        };
+        match parse_manifest(name, manifest_path, &contents, options, verbose) {
+            Ok(manifest) if has_own_lints(&manifest) => {
+                if verbose {
+                    options.report(format_args!(
+                        "{} Package {name} ({manifest_path}) defines its own lints",
+                        style::pass()
+                    ));
+                }
+                PackageStatus::Passed
+            }
+            Ok(_) => {
+                if verbose {
+                    options.report(format_args!(
+                        "{} Package {name} ({manifest_path}) doesn't define any lints",
+                        style::fail()
+                    ));
+                }
+                PackageStatus::Failed(PackageValidationErrorKind::PackageLintsMissing)
+            }
+            Err(kind) => PackageStatus::Failed(kind),
+        }
+    };
+    let mut result = PackageResult {
+        package: package.id.clone(),
+        name: package.name.clone(),
+        manifest_path: package.manifest_path.clone(),
+        status,
+        warnings: Vec::new(),
+    };
+    opt_out.warn_if_unjustified(&mut result, options, verbose);
+    severity::apply_to_package(&mut result, options);
+    result
+}
+
+/// Whether the manifest sets any lints in its own `[lints]` tables.
+fn has_own_lints(manifest: &toml::Table) -> bool {
+    manifest
+        .get("lints")
+        .and_then(toml::Value::as_table)
+        .is_some_and(|lints| {
+            lints.iter().any(|(tool, lints)| {
+                tool != "workspace" && lints.as_table().is_some_and(|lints| !lints.is_empty())
+            })
+        })
+}
+
+/// Run the built-in [`LintsCheck`] and then each of the custom checks against the package,
+/// stopping at the first one which fails.
+fn run_checks(
+    package: &cargo_metadata::Package,
+    manifest: &toml::Table,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), PackageValidationErrorKind> {
+    let lints_check = LintsCheck { options, verbose };
+    let checks = iter::once(&lints_check as &dyn WorkspaceCheck)
+        .chain(options.checks.iter().map(|check| &**check));
+    for (index, check) in checks.enumerate() {
+        if let Err(kind) = check.check(package, manifest) {
+            // The built-in check reports its own failures.
+            if verbose && index > 0 {
+                options.report(format_args!(
+                    "{} Package {} ({}): {kind}",
+                    style::fail(),
+                    package.name,
+                    package.manifest_path
+                ));
+            }
+            return Err(kind);
+        }
+    }
+    Ok(())
+}
+
+/// Scan the sources of a package for crate-level attributes which allow the workspace's lints.
+///
+/// Files which can't be read are ignored, since `cargo build` will report them anyway.
+fn source_allow_warnings(
+    package: &cargo_metadata::Package,
+    files: SourceFiles,
+    workspace_lints: &toml::Table,
+) -> Vec<Warning> {
+    let roots = package
+        .targets
+        .iter()
+        .filter(|target| {
+            !target
+                .kind
+                .iter()
+                .any(|kind| matches!(kind.as_str(), "test" | "bench" | "example" | "custom-build"))
+        })
+        .map(|target| target.src_path.clone())
+        .collect::<BTreeSet<_>>();
+    let mut paths = BTreeSet::new();
+    for root in roots.iter().cloned() {
+        match (files, root.parent()) {
+            (SourceFiles::All, Some(dir)) => path::rust_sources(dir, &mut paths),
+            _ => {
+                paths.insert(root);
+            }
+        }
+    }
+    let package_dir = package.manifest_path.parent().unwrap_or(Utf8Path::new(""));
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let lints = advisory::source_allows(&fs::read_to_string(&path).ok()?, workspace_lints);
+            if lints.is_empty() {
+                return None;
+            }
+            let lints = lints
+                .iter()
+                .map(|lint| format!("`{lint}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            // Outside the crate roots, an inner attribute only applies to its own module.
+            let applies_to = if roots.contains(&path) {
+                "crate"
+            } else {
+                "module"
+            };
+            Some(Warning {
+                scope: WarningScope::Package(package.id.clone()),
+                code: "source-allow".to_owned(),
+                message: format!(
+                    "`{}` allows {lints} for the whole {applies_to}, overriding the workspace \
+                     lints",
+                    path.strip_prefix(package_dir).unwrap_or(&path)
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Check the given package manifests, without access to the workspace root.
+///
+/// This is for setups where the member manifests exist but the workspace hasn't been assembled,
+/// so `cargo metadata` can't be run. Only the members themselves are checked. Since there is no
+/// workspace root, path prefixes in [`ValidationOptions::excludes`] are matched against the
+/// manifest paths as given.
+///
+/// # Arguments
+/// * `manifest_paths`: The paths to the `Cargo.toml` manifests of the members to check.
+/// * `options`: Options controlling which packages are checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// Returns an error if a hook couldn't be run. Packages whose manifests can't be read or parsed
+/// fail the check instead.
+pub fn check_member_manifests(
+    manifest_paths: &[Utf8PathBuf],
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Vec<PackageResult>, WorkspaceValidationError> {
+    let verbose = verbose || options.verbose;
+    options
+        .check_each(manifest_paths, verbose, |manifest_path| {
+            check_member_manifest(manifest_path, options, verbose)
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Check a single member manifest, as for [`check_member_manifests`].
+fn check_member_manifest(
+    manifest_path: &Utf8PathBuf,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<PackageResult, WorkspaceValidationError> {
+    let contents = match fs::read_to_string(manifest_path) {
+        Ok(contents) => contents,
+        Err(e) => {
+            let name = manifest_path.to_string();
+            let package = PackageId {
+                repr: format!("{name} ({manifest_path})"),
+            };
+            return Ok(unreadable_manifest(
+                &name,
+                package,
+                manifest_path,
+                e,
+                options,
+                verbose,
+            ));
+        }
+    };
+    let package_table = toml::from_str::<toml::Table>(&contents)
+        .ok()
+        .and_then(|mut manifest| manifest.remove("package"));
+    // Fall back to identifying the package by its path if we can't find its name.
+    let name = package_table
+        .as_ref()
+        .and_then(|package| package.get("name")?.as_str())
+        .map_or_else(|| manifest_path.to_string(), str::to_owned);
+    let opt_out = package_table
+        .as_ref()
+        .and_then(|package| package.get("metadata")?.get("workspace-lints"))
+        .map_or(OptOut::None, |metadata| {
+            OptOut::new(
+                metadata.get("skip") == Some(&toml::Value::Boolean(true)),
+                metadata.get("reason").and_then(toml::Value::as_str),
+            )
+        });
+    let unpublished = package_table
+        .as_ref()
+        .and_then(|package| package.get("publish"))
+        .is_some_and(|publish| match publish {
+            toml::Value::Boolean(publish) => !publish,
+            toml::Value::Array(registries) => registries.is_empty(),
+            _ => false,
+        });
+    let package = PackageId {
+        repr: format!("{name} ({manifest_path})"),
+    };
+    if let Some(reason) = skip_reason(
+        options,
+        &name,
+        manifest_path,
+        Utf8Path::new(""),
+        manifest_skip_reason(options, &opt_out, unpublished),
+        verbose,
+    ) {
+        return Ok(PackageResult {
+            package,
+            name,
+            manifest_path: manifest_path.clone(),
+            status: PackageStatus::Skipped(reason),
+            warnings: Vec::new(),
+        });
+    }
+    let mut result = check_manifest(
+        &name,
+        package,
+        manifest_path,
+        &contents,
+        options,
+        verbose,
+        |manifest| match validate_manifest(&name, manifest_path, manifest, options, verbose) {
+            Ok(()) => hook::run_hooks(&name, manifest_path, Utf8Path::new(""), options, verbose),
+            Err(kind) => Ok(Err(kind)),
+        },
+    )?;
+    opt_out.warn_if_unjustified(&mut result, options, verbose);
+    severity::apply_to_package(&mut result, options);
+    Ok(result)
+}
+
+/// The result for a package whose manifest couldn't be read.
+fn unreadable_manifest(
+    name: &str,
+    package: PackageId,
+    manifest_path: &Utf8Path,
+    error: io::Error,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> PackageResult {
+    if verbose {
+        options.report(format_args!(
+            "{} Package {name} ({manifest_path}) couldn't be read: {error}",
+            style::fail()
+        ));
+    }
+    PackageResult {
+        package,
+        name: name.to_owned(),
+        manifest_path: manifest_path.to_owned(),
+        status: PackageStatus::Failed(PackageValidationErrorKind::ManifestReadError(error)),
+        warnings: Vec::new(),
+    }
+}
+
+/// How a package opts out of being checked with `package.metadata.workspace-lints`.
+pub(crate) enum OptOut {
+    /// The package doesn't opt out.
+    None,
+    /// The package sets `skip = true`, with this `reason`.
+    Justified(String),
+    /// The package sets `skip = true` without a `reason`, so is checked anyway.
+    Unjustified,
+}
+
+impl OptOut {
+    /// Interpret whether the package sets `skip = true`, and the `reason` it gives, if any.
+    fn new(skip: bool, reason: Option<&str>) -> Self {
+        if !skip {
+            return Self::None;
+        }
+        match reason.map(str::trim) {
+            Some(reason) if !reason.is_empty() => Self::Justified(reason.to_owned()),
+            _ => Self::Unjustified,
+        }
+    }
+
+    /// Read how a package opts out from its metadata, as given by `cargo metadata`.
+    pub(crate) fn from_metadata(metadata: &serde_json::Value) -> Self {
+        metadata
+            .get("workspace-lints")
+            .map_or(Self::None, |metadata| {
+                Self::new(
+                    metadata.get("skip") == Some(&serde_json::Value::Bool(true)),
+                    metadata.get("reason").and_then(serde_json::Value::as_str),
+                )
+            })
+    }
+
+    /// Warn that a package which tried to opt out without a reason was checked anyway.
+    fn warn_if_unjustified(
+        &self,
+        result: &mut PackageResult,
+        options: &ValidationOptions,
+        verbose: bool,
+    ) {
+        if !matches!(self, Self::Unjustified) {
+            return;
+        }
+        let warning = Warning {
+            scope: WarningScope::Package(result.package.clone()),
+            code: "unjustified-skip".to_owned(),
+            message: "sets `package.metadata.workspace-lints.skip` without a `reason`, so was \
+                      checked anyway"
+                .to_owned(),
+        };
+        if verbose {
+            options.report(format_args!(
+                "WARN: Package {} ({}): {warning}",
+                result.name, result.manifest_path
+            ));
+        }
+        result.warnings.push(warning);
+    }
+}
+
+/// Why the package's own manifest says it should be skipped, if it does.
+fn manifest_skip_reason(
+    options: &ValidationOptions,
+    opt_out: &OptOut,
+    unpublished: bool,
+) -> Option<SkipReason> {
+    if let OptOut::Justified(reason) = opt_out {
+        Some(SkipReason::OptedOut {
+            reason: reason.clone(),
+        })
+    } else if unpublished && !options.include_unpublished {
+        Some(SkipReason::Unpublished)
+    } else {
+        None
+    }
+}
+
+/// Check whether the given package should be skipped, reporting it if so.
+///
+/// `manifest_reason` is the reason the package's manifest gives for skipping it, if any.
+fn skip_reason(
+    options: &ValidationOptions,
+    name: &str,
+    manifest_path: &Utf8Path,
+    workspace_root: &Utf8Path,
+    manifest_reason: Option<SkipReason>,
+    verbose: bool,
+) -> Option<SkipReason> {
+    let reason = manifest_reason
+        .or_else(|| {
+            (options.workspace_root_only
+                && !workspace_root.as_str().is_empty()
+                && !path::is_within(manifest_path, workspace_root))
+            .then_some(SkipReason::OutsideWorkspaceRoot)
+        })
+        .or_else(|| {
+            options
+                .excludes
+                .iter()
+                .any(|pattern| pattern.matches(name, manifest_path, workspace_root))
+                .then_some(SkipReason::Excluded)
+        })
+        .or_else(|| {
+            options
+                .changed_manifests
+                .as_ref()
+                .is_some_and(|changed| !changed.iter().any(|path| path == manifest_path))
+                .then_some(SkipReason::Unchanged)
+        })?;
+    if verbose {
+        options.report(format_args!(
+            "SKIP: Package {name} ({manifest_path}) {reason}"
+        ));
     }
+    Some(reason)
+}
+
+/// Check the contents of a single package's manifest, parsing it once to give to `check`.
+fn check_manifest(
+    name: &str,
+    package: PackageId,
+    manifest_path: &Utf8Path,
+    contents: &str,
+    options: &ValidationOptions,
+    verbose: bool,
+    check: impl FnOnce(
+        &toml::Table,
+    ) -> Result<Result<(), PackageValidationErrorKind>, WorkspaceValidationError>,
+) -> Result<PackageResult, WorkspaceValidationError> {
+    let mut warnings = Vec::new();
+    let result = match parse_manifest(name, manifest_path, contents, options, verbose) {
+        Ok(manifest) => {
+            if options.check_priority_overrides {
+                warnings.extend(advisory::priority_overrides(&manifest).into_iter().map(
+                    |message| Warning {
+                        scope: WarningScope::Package(package.clone()),
+                        code: "priority-override".to_owned(),
+                        message,
+                    },
+                ));
+            }
+            if options.check_negating_overrides {
+                warnings.extend(advisory::negating_overrides(&manifest).into_iter().map(
+                    |message| Warning {
+                        scope: WarningScope::Package(package.clone()),
+                        code: "negating-override".to_owned(),
+                        message,
+                    },
+                ));
+            }
+            check(&manifest)?
+        }
+        Err(kind) => Err(kind),
+    };
+    if verbose {
+        for warning in &warnings {
+            options.report(format_args!(
+                "WARN: Package {name} ({manifest_path}): {warning}"
+            ));
+        }
+    }
+    Ok(PackageResult {
+        package,
+        name: name.to_owned(),
+        manifest_path: manifest_path.to_owned(),
+        status: match result {
+            Ok(()) => PackageStatus::Passed,
+            Err(kind) => PackageStatus::Failed(kind),
+        },
+        warnings,
+    })
+}
+
+/// Parse the contents of a package's manifest, reporting why the package fails if it can't be
+/// parsed.
+fn parse_manifest(
+    name: &str,
+    manifest_path: &Utf8Path,
+    contents: &str,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<toml::Table, PackageValidationErrorKind> {
+    toml::from_str(contents).map_err(|e| {
+        // A duplicated `[lints]` table is a more specific mistake than a general syntax error.
+        let Some((first_line, second_line)) = find_duplicate_lints_tables(contents) else {
+            if verbose {
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) couldn't be parsed: {}",
+                    style::fail(),
+                    e.message()
+                ));
+            }
+            return PackageValidationErrorKind::ManifestParseError(e);
+        };
+        if verbose {
+            options.report(format_args!(
+                "{} Package {name} ({manifest_path}) has `[lints]` on lines {first_line} and \
+                 {second_line}",
+                style::fail()
+            ));
+        }
+        PackageValidationErrorKind::DuplicateLintsTable {
+            first_line,
+            second_line,
+        }
+    })
+}
+
+/// Validate that the given manifest, as a string of TOML, has `lints.workspace = true`.
+///
+/// This runs the same check as [`validate_package_manifest`], but doesn't need any package
+/// details from `cargo metadata`. Hooks aren't run, since there is no manifest file to run them
+/// on.
+///
+/// # Arguments
+/// * `name`: The name of the package, for verbose output.
+/// * `manifest`: The contents of the package's `Cargo.toml` manifest.
+/// * `options`: Options controlling how the package is checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// If the validation fails, it returns an error indicating the kind of failure, including if the
+/// manifest couldn't be parsed.
+pub fn validate_manifest_str(
+    name: &str,
+    manifest: &str,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), PackageValidationErrorKind> {
+    let verbose = verbose || options.verbose;
+    let manifest_path = Utf8Path::new("<string>");
+    let manifest = parse_manifest(name, manifest_path, manifest, options, verbose)?;
+    validate_manifest(name, manifest_path, &manifest, options, verbose)
 }
 
 /// Validate that the given package has `lints.workspace = true`.
@@ -54,65 +1619,418 @@ pub fn validate_workspace(
 ///
 /// # Errors
 /// If the validation fails, it returns an error indicating the kind of failure.
+#[deprecated(
+    note = "use `validate_package_manifest`, which takes `ValidationOptions` to check with"
+)]
 pub fn validate_package(
     package: &cargo_metadata::Package,
     manifest: &toml::Table,
     verbose: bool,
 ) -> Result<(), PackageValidationErrorKind> {
-    match manifest
-        .get("lints")
-        .and_then(|lints| lints.get("workspace"))
-    {
-        Some(toml::Value::Boolean(true)) => {
+    validate_package_manifest(package, manifest, &ValidationOptions::default(), verbose)
+}
+
+/// Validate that the given package's manifest sets every required key, as with the given options.
+///
+/// # Arguments
+/// * `package`: The package details, as returned by [`cargo_metadata`].
+/// * `manifest`: The `Cargo.toml` manifest for this package, parsed as `toml`.
+/// * `options`: Options controlling how the package is checked.
+/// * `verbose`: If set to true, reports more detailed progress to
+///   [`ValidationOptions::reporter`].
+///
+/// # Errors
+/// If the validation fails, it returns an error indicating the kind of failure.
+pub fn validate_package_manifest(
+    package: &cargo_metadata::Package,
+    manifest: &toml::Table,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), PackageValidationErrorKind> {
+    let verbose = verbose || options.verbose;
+    validate_manifest(
+        &package.name,
+        &package.manifest_path,
+        manifest,
+        options,
+        verbose,
+    )
+}
+
+/// Validate that the given manifest sets every required key to `true`.
+///
+/// The required keys are [`ValidationOptions::required_keys`], or just `lints.workspace` if there
+/// are none.
+fn validate_manifest(
+    name: &str,
+    manifest_path: &Utf8Path,
+    manifest: &toml::Table,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), PackageValidationErrorKind> {
+    let default_keys = [DEFAULT_REQUIRED_KEY.to_owned()];
+    let required_keys = if options.required_keys.is_empty() {
+        &default_keys[..]
+    } else {
+        &options.required_keys
+    };
+    let mut notes = Vec::new();
+    for key in required_keys {
+        if key == DEFAULT_REQUIRED_KEY {
+            notes.extend(validate_lints_workspace(
+                name,
+                manifest_path,
+                manifest,
+                options,
+                verbose,
+            )?);
+        } else {
+            validate_required_key(name, manifest_path, manifest, key, options, verbose)?;
+        }
+    }
+    for field in &options.inherited_fields {
+        let inherited = manifest
+            .get("package")
+            .and_then(|package| package.get(field))
+            .and_then(|field| field.get("workspace"))
+            == Some(&toml::Value::Boolean(true));
+        if !inherited {
             if verbose {
-                eprintln!(
-                    "PASS: Package {} ({})",
-                    package.name,
-                    package.manifest_path.as_str()
-                );
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) doesn't inherit `package.{field}`",
+                    style::fail()
+                ));
             }
-            Ok(())
+            return Err(PackageValidationErrorKind::FieldNotInherited(field.clone()));
+        }
+    }
+    if verbose {
+        if notes.is_empty() {
+            options.report(format_args!(
+                "{} Package {name} ({manifest_path})",
+                style::pass()
+            ));
+        } else {
+            options.report(format_args!(
+                "{} Package {name} ({manifest_path}) {}",
+                style::pass(),
+                notes.join(", ")
+            ));
         }
+    }
+    Ok(())
+}
+
+/// The key which is required to be `true` if no others are given.
+const DEFAULT_REQUIRED_KEY: &str = "lints.workspace";
+
+/// Validate that the given manifest has `lints.workspace = true` (or an allowed value).
+///
+/// If the package passes because of an allowed value, returns a note saying so.
+fn validate_lints_workspace(
+    name: &str,
+    manifest_path: &Utf8Path,
+    manifest: &toml::Table,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<Option<String>, PackageValidationErrorKind> {
+    let lints = manifest.get("lints");
+    match lints.and_then(|lints| lints.get("workspace")) {
+        Some(toml::Value::Boolean(true)) => {
+            let local_tools = lints
+                .and_then(toml::Value::as_table)
+                .filter(|_| options.reject_local_lints)
+                .map(|lints| {
+                    lints
+                        .iter()
+                        .filter(|(tool, tool_lints)| {
+                            *tool != "workspace"
+                                && tool_lints.as_table().map_or(true, |tool_lints| {
+                                    tool_lints.keys().any(|lint| {
+                                        !options
+                                            .allowed_local_lints
+                                            .iter()
+                                            .any(|pattern| pattern.matches(name, tool, lint))
+                                    })
+                                })
+                        })
+                        .map(|(tool, _)| tool.clone())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if local_tools.is_empty() {
+                Ok(None)
+            } else {
+                if verbose {
+                    options.report(format_args!(
+                        "{} Package {name} ({manifest_path}) defines its own lints alongside the \
+                         workspace's",
+                        style::fail()
+                    ));
+                }
+                Err(PackageValidationErrorKind::LocalLintsPresent(local_tools))
+            }
+        }
+        Some(other_value) if options.allowed_values.contains(other_value) => Ok(Some(format!(
+            "has allowed `lints.workspace = {other_value}`"
+        ))),
         Some(other_value) => {
             if verbose {
-                eprintln!(
-                    "FAIL: Package {} ({}) has `lints.workspace = {other_value}`",
-                    package.name,
-                    package.manifest_path.as_str()
-                );
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) has `lints.workspace = {other_value}`",
+                    style::fail()
+                ));
             }
             Err(PackageValidationErrorKind::WorkspaceLintsWrongValue(
                 other_value.clone(),
             ))
         }
         None => {
+            let inline_tools = lints
+                .and_then(toml::Value::as_table)
+                .filter(|_| options.detect_inline_lints)
+                .map(|lints| lints.keys().cloned().collect::<Vec<_>>())
+                .unwrap_or_default();
+            if inline_tools.is_empty() {
+                if verbose {
+                    options.report(format_args!(
+                        "{} Package {name} ({manifest_path}) missing `lints.workspace` field",
+                        style::fail()
+                    ));
+                }
+                Err(PackageValidationErrorKind::WorkspaceLintsMissing)
+            } else {
+                if verbose {
+                    options.report(format_args!(
+                        "{} Package {name} ({manifest_path}) defines its own lints instead of \
+                         inheriting them",
+                        style::fail()
+                    ));
+                }
+                Err(PackageValidationErrorKind::InlineLints(inline_tools))
+            }
+        }
+    }
+}
+
+/// Validate that the given dotted key (such as `package.edition.workspace`) is `true` in the
+/// manifest.
+fn validate_required_key(
+    name: &str,
+    manifest_path: &Utf8Path,
+    manifest: &toml::Table,
+    key: &str,
+    options: &ValidationOptions,
+    verbose: bool,
+) -> Result<(), PackageValidationErrorKind> {
+    let mut segments = key.split('.');
+    let value = segments.next().and_then(|first| {
+        segments.try_fold(manifest.get(first)?, |value, segment| {
+            value.as_table()?.get(segment)
+        })
+    });
+    match value {
+        Some(toml::Value::Boolean(true)) => Ok(()),
+        Some(other_value) => {
             if verbose {
-                eprintln!(
-                    "FAIL: Package {} ({}) missing `lints.workspace` field",
-                    package.name,
-                    package.manifest_path.as_str()
-                );
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) has `{key} = {other_value}`",
+                    style::fail()
+                ));
+            }
+            Err(PackageValidationErrorKind::RequiredKeyWrongValue {
+                key: key.to_owned(),
+                found: other_value.clone(),
+            })
+        }
+        None => {
+            if verbose {
+                options.report(format_args!(
+                    "{} Package {name} ({manifest_path}) missing `{key}` field",
+                    style::fail()
+                ));
             }
-            Err(PackageValidationErrorKind::WorkspaceLintsMissing)
+            Err(PackageValidationErrorKind::RequiredKeyMissing(
+                key.to_owned(),
+            ))
         }
     }
 }
 
+/// Find the (1-indexed) line numbers of the first two `[lints]` table headers in a raw manifest.
+///
+/// Returns `None` if the manifest doesn't define `[lints]` more than once.
+fn find_duplicate_lints_tables(contents: &str) -> Option<(usize, usize)> {
+    let mut headers = contents.lines().enumerate().filter_map(|(index, line)| {
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        let name = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+        (name == "lints").then_some(index + 1)
+    });
+    Some((headers.next()?, headers.next()?))
+}
+
+/// The outcome of checking a single package.
+#[derive(Debug)]
+pub struct PackageResult {
+    /// Which package was checked.
+    pub package: PackageId,
+    /// The name of the package.
+    pub name: String,
+    /// The path to the package's `Cargo.toml` manifest.
+    pub manifest_path: Utf8PathBuf,
+    /// Whether the package passed the check, and if not, why.
+    pub status: PackageStatus,
+    /// Problems found by advisory checks, which don't cause the package to fail.
+    ///
+    /// These all have the scope [`WarningScope::Package`].
+    pub warnings: Vec<Warning>,
+}
+
+/// Whether a package passed the check.
+#[derive(Debug)]
+pub enum PackageStatus {
+    /// The package passed the check.
+    Passed,
+    /// The package failed the check.
+    Failed(PackageValidationErrorKind),
+    /// The package wasn't checked.
+    Skipped(SkipReason),
+}
+
+/// Why a package might not be checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The package matched one of [`ValidationOptions::excludes`], or one of the patterns in
+    /// `workspace.metadata.workspace-lints.exclude`.
+    Excluded,
+    /// The package sets `package.metadata.workspace-lints.skip = true` in its manifest, with a
+    /// non-empty `reason` beside it.
+    ///
+    /// Packages which set `skip = true` without a reason are checked anyway, with a warning.
+    OptedOut {
+        /// The reason the package gives for opting out.
+        reason: String,
+    },
+    /// The package can't be published, and [`ValidationOptions::include_unpublished`] isn't set.
+    Unpublished,
+    /// The package's manifest isn't inside the workspace root, and
+    /// [`ValidationOptions::workspace_root_only`] is set.
+    OutsideWorkspaceRoot,
+    /// The package fails in the same way as recorded in a [`Baseline`] of known failures.
+    Baselined,
+    /// The package's manifest isn't one of [`ValidationOptions::changed_manifests`].
+    Unchanged,
+}
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Excluded => f.write_str("is excluded"),
+            Self::OptedOut { reason } => write!(
+                f,
+                "opts out with `package.metadata.workspace-lints.skip`, because: {reason}"
+            ),
+            Self::Unpublished => f.write_str("is unpublished"),
+            Self::OutsideWorkspaceRoot => f.write_str("is outside the workspace root"),
+            Self::Baselined => f.write_str("is a known failure in the baseline"),
+            Self::Unchanged => f.write_str("is unchanged"),
+        }
+    }
+}
+
+/// The outcome of checking every package in a workspace.
+#[derive(Debug)]
+pub struct WorkspaceReport {
+    /// The root directory of the workspace, if it is known.
+    pub workspace_root: Option<Utf8PathBuf>,
+    /// The outcome of checking each package.
+    pub packages: Vec<ConfiguredPackageResult>,
+    /// Problems found by advisory checks on the workspace as a whole.
+    ///
+    /// These all have the scope [`WarningScope::Workspace`]. Warnings about individual packages
+    /// are in [`PackageResult::warnings`].
+    pub warnings: Vec<Warning>,
+}
+
+/// The outcome of checking a single package, when checking under several configurations.
+#[derive(Debug)]
+pub struct ConfiguredPackageResult {
+    /// The outcome of checking the package.
+    pub result: PackageResult,
+    /// The labels of every configuration which surfaced this package.
+    pub configurations: Vec<String>,
+}
+
 /// All the reasons why we might fail a workspace.
 #[derive(Debug)]
 pub enum WorkspaceValidationError {
-    /// IO error.
-    Io(io::Error),
+    /// IO error reading the file or directory at `path`.
+    ///
+    /// The path is empty if the error was converted with [`From`], without knowing it.
+    ///
+    /// Failures to read a member's manifest are reported as
+    /// [`PackageValidationErrorKind::ManifestReadError`] instead.
+    Io {
+        /// The file or directory which couldn't be read.
+        path: Utf8PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
     /// Error running `cargo metadata`.
     CargoMetadata(cargo_metadata::Error),
-    /// Error parsing `Cargo.toml` manifest as TOML
-    Toml(toml::de::Error),
+    /// Error parsing the `Cargo.toml` manifest at `path` as TOML
+    ///
+    /// The path is empty if the error was converted with [`From`], without knowing it.
+    ///
+    /// Failures to parse a member's manifest are reported as
+    /// [`PackageValidationErrorKind::ManifestParseError`] instead.
+    Toml {
+        /// The manifest which couldn't be parsed.
+        path: Utf8PathBuf,
+        /// The underlying error.
+        error: toml::de::Error,
+    },
     /// Packages successfully read but failed the check.
     FailingPackages(Vec<PackageValidationError>),
+    /// The workspace root manifest (at the given path) doesn't define any `[workspace.lints]`,
+    /// so packages inheriting them aren't linted.
+    WorkspaceLintsUndefined(Utf8PathBuf),
+    /// The manifest at the given path is a single package with no `[workspace]` table, so there
+    /// are no `[workspace.lints]` for it to inherit.
+    ///
+    /// This isn't reported if [`ValidationOptions::allow_single_package`] is set.
+    NotAWorkspace(Utf8PathBuf),
+    /// The lints in the root `[workspace.lints]` don't follow the policy set in the
+    /// [`ValidationOptions`], such as [`ValidationOptions::required_lints`].
+    RootLintsPolicy {
+        /// The workspace root manifest.
+        path: Utf8PathBuf,
+        /// A description of each way the lints don't follow the policy.
+        problems: Vec<String>,
+    },
+}
+impl WorkspaceValidationError {
+    /// Whether this error is because the workspace fails the policy, rather than because the
+    /// check couldn't be run.
+    ///
+    /// Besides failing packages, this includes a root without any `[workspace.lints]` to inherit
+    /// (or no `[workspace]` at all), and root lints which break the policy.
+    #[must_use]
+    pub fn is_policy_violation(&self) -> bool {
+        match self {
+            Self::FailingPackages(_)
+            | Self::WorkspaceLintsUndefined(_)
+            | Self::NotAWorkspace(_)
+            | Self::RootLintsPolicy { .. } => true,
+            Self::Io { .. } | Self::CargoMetadata(_) | Self::Toml { .. } => false,
+        }
+    }
 }
 impl From<io::Error> for WorkspaceValidationError {
     fn from(error: io::Error) -> Self {
-        Self::Io(error)
+        Self::Io {
+            path: Utf8PathBuf::new(),
+            error,
+        }
     }
 }
 impl From<cargo_metadata::Error> for WorkspaceValidationError {
@@ -122,21 +2040,30 @@ impl From<cargo_metadata::Error> for WorkspaceValidationError {
 }
 impl From<toml::de::Error> for WorkspaceValidationError {
     fn from(error: toml::de::Error) -> Self {
-        Self::Toml(error)
+        Self::Toml {
+            path: Utf8PathBuf::new(),
+            error,
+        }
     }
 }
 impl fmt::Display for WorkspaceValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Io(e) => f.write_fmt(format_args!(
-                "Disk I/O Error reading `Cargo.toml` files:\n    {e}\n"
+            Self::Io { path, error } if path.as_str().is_empty() => f.write_fmt(format_args!(
+                "Disk I/O Error reading `Cargo.toml` files:\n    {error}\n"
+            )),
+            Self::Io { path, error } => f.write_fmt(format_args!(
+                "Disk I/O Error reading {path}:\n    {error}\n"
             )),
             Self::CargoMetadata(e) => f.write_fmt(format_args!(
                 "Error reading Cargo manifest data:\n    {e}\n"
             )),
-            Self::Toml(e) => f.write_fmt(format_args!(
-                "Error parsing `Cargo.toml` files as TOML:\n    {e}\n"
+            Self::Toml { path, error } if path.as_str().is_empty() => f.write_fmt(format_args!(
+                "Error parsing `Cargo.toml` files as TOML:\n    {error}\n"
             )),
+            Self::Toml { path, error } => {
+                f.write_fmt(format_args!("Error parsing {path} as TOML:\n    {error}\n"))
+            }
             Self::FailingPackages(package_failures) => {
                 f.write_str("Failing packages:")?;
                 for failure in package_failures {
@@ -144,17 +2071,74 @@ impl fmt::Display for WorkspaceValidationError {
                 }
                 Ok(())
             }
+            Self::WorkspaceLintsUndefined(manifest_path) => f.write_fmt(format_args!(
+                "No lints defined in `[workspace.lints]` of the workspace root:\n    \
+                 {manifest_path}\n"
+            )),
+            Self::NotAWorkspace(manifest_path) => f.write_fmt(format_args!(
+                "Not a workspace, but a single package with no `[workspace]` table, so it has no \
+                 `[workspace.lints]` to inherit:\n    {manifest_path}\n"
+            )),
+            Self::RootLintsPolicy { path, problems } => {
+                f.write_fmt(format_args!(
+                    "The lints in `[workspace.lints]` of the workspace root don't follow the \
+                     policy:\n    {path}"
+                ))?;
+                for problem in problems {
+                    f.write_fmt(format_args!("\n* {problem}"))?;
+                }
+                f.write_str("\n")
+            }
+        }
+    }
+}
+impl error::Error for WorkspaceValidationError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::CargoMetadata(error) => Some(error),
+            Self::Toml { error, .. } => Some(error),
+            // There can only be one source, so it's the first failure.
+            Self::FailingPackages(failures) => failures
+                .first()
+                .map(|failure| failure as &(dyn error::Error + 'static)),
+            Self::WorkspaceLintsUndefined(_)
+            | Self::NotAWorkspace(_)
+            | Self::RootLintsPolicy { .. } => None,
         }
     }
 }
 
 /// A package failed the check.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct PackageValidationError {
     /// Why the package failed.
+    #[serde(flatten)]
     kind: PackageValidationErrorKind,
     /// Which package failed.
     package: PackageId,
+    /// The path to the failing package's `Cargo.toml` manifest.
+    manifest_path: Utf8PathBuf,
+}
+
+impl PackageValidationError {
+    /// Why the package failed.
+    #[must_use]
+    pub fn kind(&self) -> &PackageValidationErrorKind {
+        &self.kind
+    }
+
+    /// Which package failed.
+    #[must_use]
+    pub fn package(&self) -> &PackageId {
+        &self.package
+    }
+
+    /// The path to the failing package's `Cargo.toml` manifest.
+    #[must_use]
+    pub fn manifest_path(&self) -> &Utf8Path {
+        &self.manifest_path
+    }
 }
 
 impl fmt::Display for PackageValidationError {
@@ -165,15 +2149,141 @@ impl fmt::Display for PackageValidationError {
         ))
     }
 }
-impl error::Error for PackageValidationError {}
+impl error::Error for PackageValidationError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
 
 /// Why a package might fail the check.
-#[derive(Debug)]
+///
+/// This serializes with a `kind` tag naming the variant, and any details in `value`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
 pub enum PackageValidationErrorKind {
     /// There was no `lints.workspace` field.
+    #[serde(rename = "missing")]
     WorkspaceLintsMissing,
     /// The `lints.workspace` field was provided, but had the wrong value.
+    #[serde(rename = "wrong_value")]
     WorkspaceLintsWrongValue(toml::Value),
+    /// There was no `lints.workspace` field, but the package defines its own lints for the
+    /// contained tools.
+    ///
+    /// This is only reported if [`ValidationOptions::detect_inline_lints`] is set.
+    InlineLints(Vec<String>),
+    /// The package isn't in a workspace, and doesn't define any lints of its own.
+    ///
+    /// This is only reported if [`ValidationOptions::allow_single_package`] is set.
+    PackageLintsMissing,
+    /// One of [`ValidationOptions::hooks`] exited unsuccessfully for this package.
+    HookFailed {
+        /// The hook which failed.
+        hook: String,
+        /// The hook's exit code, if it exited with one.
+        exit_code: Option<i32>,
+    },
+    /// The manifest couldn't be parsed because it defines the `[lints]` table more than once.
+    DuplicateLintsTable {
+        /// The line on which the first `[lints]` table starts.
+        first_line: usize,
+        /// The line on which the second `[lints]` table starts.
+        second_line: usize,
+    },
+    /// The package has `lints.workspace = true`, but also defines its own lints for the contained
+    /// tools, which take precedence over the workspace's.
+    ///
+    /// This is only reported if [`ValidationOptions::reject_local_lints`] is set.
+    LocalLintsPresent(Vec<String>),
+    /// One of [`ValidationOptions::required_keys`] (other than `lints.workspace`) was missing.
+    RequiredKeyMissing(String),
+    /// One of [`ValidationOptions::required_keys`] (other than `lints.workspace`) was provided,
+    /// but wasn't `true`.
+    RequiredKeyWrongValue {
+        /// The dotted path to the key.
+        key: String,
+        /// The value the key had instead.
+        found: toml::Value,
+    },
+    /// One of [`ValidationOptions::inherited_fields`] wasn't inherited from the workspace.
+    FieldNotInherited(String),
+    /// One of the custom [`ValidationOptions::checks`] failed.
+    CheckFailed {
+        /// The name of the check, from [`WorkspaceCheck::name`].
+        check: String,
+        /// Why the package failed the check.
+        reason: String,
+    },
+    /// The manifest couldn't be read.
+    ManifestReadError(#[serde(serialize_with = "serialize_display")] io::Error),
+    /// The manifest couldn't be parsed as TOML.
+    ManifestParseError(#[serde(serialize_with = "serialize_display")] toml::de::Error),
+}
+impl PackageValidationErrorKind {
+    /// Guidance on how to fix a package which fails for this reason.
+    #[must_use]
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::WorkspaceLintsMissing => {
+                "add the following to the package's `Cargo.toml`:\n    [lints]\n    \
+                 workspace = true"
+            }
+            Self::WorkspaceLintsWrongValue(_) => {
+                "set `workspace = true` in the `[lints]` table of the package's `Cargo.toml`"
+            }
+            Self::InlineLints(_) => {
+                "move the package's lints into `[workspace.lints]` in the workspace root, and \
+                 replace the package's `[lints]` tables with:\n    [lints]\n    workspace = true"
+            }
+            Self::PackageLintsMissing => {
+                "add the package's lints to its `Cargo.toml`, such as:\n    [lints.rust]\n    \
+                 unsafe_code = \"forbid\""
+            }
+            Self::HookFailed { .. } => "see the hook's output for why it failed",
+            Self::DuplicateLintsTable { .. } => {
+                "merge the `[lints]` tables into one with `workspace = true`"
+            }
+            Self::LocalLintsPresent(_) => {
+                "move the package's lints into `[workspace.lints]` in the workspace root, and \
+                 remove the package's other `[lints]` tables"
+            }
+            Self::RequiredKeyMissing(_) | Self::RequiredKeyWrongValue { .. } => {
+                "set the key to `true` in the package's `Cargo.toml`, to inherit it from the \
+                 workspace"
+            }
+            Self::FieldNotInherited(_) => {
+                "set `workspace = true` for the field in the `[package]` table of the package's \
+                 `Cargo.toml`, and give its value in `[workspace.package]` in the workspace root"
+            }
+            Self::CheckFailed { .. } => "see the check's reason for how to fix the package",
+            Self::ManifestReadError(_) => "check that the manifest exists and is readable",
+            Self::ManifestParseError(_) => "fix the syntax error in the package's `Cargo.toml`",
+        }
+    }
+}
+impl PackageValidationErrorKind {
+    /// The name of the check which found this problem, for [`ValidationOptions::severities`].
+    ///
+    /// Returns `None` for manifests which couldn't be read or parsed, since those can't be
+    /// checked at all.
+    #[must_use]
+    pub fn check_name(&self) -> Option<&str> {
+        match self {
+            Self::WorkspaceLintsMissing
+            | Self::WorkspaceLintsWrongValue(_)
+            | Self::InlineLints(_)
+            | Self::PackageLintsMissing
+            | Self::LocalLintsPresent(_)
+            | Self::RequiredKeyMissing(_)
+            | Self::RequiredKeyWrongValue { .. }
+            | Self::FieldNotInherited(_) => Some("lints"),
+            Self::HookFailed { .. } => Some("hooks"),
+            Self::CheckFailed { check, .. } => Some(check),
+            Self::DuplicateLintsTable { .. }
+            | Self::ManifestReadError(_)
+            | Self::ManifestParseError(_) => None,
+        }
+    }
 }
 impl fmt::Display for PackageValidationErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -182,6 +2292,340 @@ impl fmt::Display for PackageValidationErrorKind {
             Self::WorkspaceLintsWrongValue(found) => {
                 f.write_fmt(format_args!("workspace.lints = {found}, expected `true`"))
             }
+            Self::InlineLints(tools) => f.write_fmt(format_args!(
+                "Lints defined inline for {} instead of `lints.workspace = true`",
+                tools
+                    .iter()
+                    .map(|tool| format!("`lints.{tool}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Self::PackageLintsMissing => f.write_str("No lints defined in `[lints]`"),
+            Self::HookFailed {
+                hook,
+                exit_code: Some(code),
+            } => f.write_fmt(format_args!("Hook `{hook}` failed with exit code {code}")),
+            Self::HookFailed {
+                hook,
+                exit_code: None,
+            } => f.write_fmt(format_args!("Hook `{hook}` was terminated")),
+            Self::DuplicateLintsTable {
+                first_line,
+                second_line,
+            } => f.write_fmt(format_args!(
+                "`[lints]` table defined twice, on lines {first_line} and {second_line}"
+            )),
+            Self::LocalLintsPresent(tools) => f.write_fmt(format_args!(
+                "Lints defined locally for {} alongside `lints.workspace = true`",
+                tools
+                    .iter()
+                    .map(|tool| format!("`lints.{tool}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Self::RequiredKeyMissing(key) => f.write_fmt(format_args!("No `{key}` field found")),
+            Self::RequiredKeyWrongValue { key, found } => {
+                f.write_fmt(format_args!("{key} = {found}, expected `true`"))
+            }
+            Self::FieldNotInherited(field) => f.write_fmt(format_args!(
+                "`package.{field}` isn't inherited with `{field}.workspace = true`"
+            )),
+            Self::CheckFailed { check, reason } => {
+                f.write_fmt(format_args!("Check `{check}` failed: {reason}"))
+            }
+            Self::ManifestReadError(e) => f.write_fmt(format_args!("Couldn't read manifest: {e}")),
+            Self::ManifestParseError(e) => f.write_fmt(format_args!(
+                "Couldn't parse manifest as TOML: {}",
+                e.message()
+            )),
+        }
+    }
+}
+impl error::Error for PackageValidationErrorKind {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ManifestReadError(error) => Some(error),
+            Self::ManifestParseError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Serialize a value as its [`Display`](fmt::Display) output, for errors which can't be
+/// serialized directly.
+fn serialize_display<S: serde::Serializer>(
+    value: &impl fmt::Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether the result of checking a manifest is as expected.
+    type Expectation = fn(&Result<(), PackageValidationErrorKind>) -> bool;
+
+    /// Check a manifest, made of a `[package]` table followed by `rest`, with the default options.
+    fn validate(rest: &str) -> Result<(), PackageValidationErrorKind> {
+        let manifest = format!("[package]\nname = \"test\"\nversion = \"0.1.0\"\n{rest}");
+        validate_manifest_str("test", &manifest, &ValidationOptions::default(), false)
+    }
+
+    #[test]
+    fn validate_manifest_str_lints_workspace() {
+        let cases: &[(&str, Expectation)] = &[
+            ("[lints]\nworkspace = true\n", Result::is_ok),
+            ("[lints.rust]\n[lints]\nworkspace = true\n", Result::is_ok),
+            ("[lints]\nworkspace = false\n", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::WorkspaceLintsWrongValue(
+                        toml::Value::Boolean(false)
+                    ))
+                )
+            }),
+            ("[lints]\nworkspace = \"true\"\n", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::WorkspaceLintsWrongValue(
+                        toml::Value::String(value)
+                    )) if value == "true"
+                )
+            }),
+            ("", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::WorkspaceLintsMissing)
+                )
+            }),
+            ("[lints.rust]\nunsafe_code = \"forbid\"\n", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::WorkspaceLintsMissing)
+                )
+            }),
+            ("lints = [\"workspace\"]\n", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::WorkspaceLintsMissing)
+                )
+            }),
+            ("[lints\nworkspace = true\n", |result| {
+                matches!(
+                    result,
+                    Err(PackageValidationErrorKind::ManifestParseError(_))
+                )
+            }),
+            (
+                "[lints]\nworkspace = true\n[lints]\nworkspace = true\n",
+                |result| {
+                    matches!(
+                        result,
+                        Err(PackageValidationErrorKind::DuplicateLintsTable {
+                            first_line: 4,
+                            second_line: 6,
+                        })
+                    )
+                },
+            ),
+        ];
+        for (rest, expected) in cases {
+            let result = validate(rest);
+            assert!(expected(&result), "{rest:?} gave {result:?}");
+        }
+    }
+
+    #[test]
+    fn validate_manifest_str_allowed_values() {
+        let options = ValidationOptions {
+            allowed_values: vec![toml::Value::Boolean(false)],
+            ..ValidationOptions::default()
+        };
+        let manifest = "[package]\nname = \"test\"\n[lints]\nworkspace = false\n";
+        assert!(validate_manifest_str("test", manifest, &options, false).is_ok());
+    }
+
+    #[test]
+    fn validate_manifest_str_inline_lints() {
+        let options = ValidationOptions {
+            detect_inline_lints: true,
+            ..ValidationOptions::default()
+        };
+        let manifest = "[package]\nname = \"test\"\n[lints.clippy]\npedantic = \"warn\"\n";
+        assert!(matches!(
+            validate_manifest_str("test", manifest, &options, false),
+            Err(PackageValidationErrorKind::InlineLints(tools)) if tools == ["clippy"]
+        ));
+    }
+
+    /// Collects the lines reported to it.
+    #[derive(Debug, Default)]
+    struct CollectingReporter(std::sync::Mutex<Vec<String>>);
+
+    impl Reporter for CollectingReporter {
+        fn report(&self, line: &str) {
+            self.0.lock().unwrap().push(line.to_owned());
+        }
+    }
+
+    #[test]
+    fn check_each_reports_verbose_lines_in_order() {
+        let reporter = Arc::new(CollectingReporter::default());
+        let options = ValidationOptions {
+            reporter: Some(reporter.clone()),
+            ..ValidationOptions::default()
+        };
+        let items = (0..32_u64).collect::<Vec<_>>();
+        let results = options.check_each(&items, true, |&item| {
+            // Make the earlier items finish last.
+            thread::sleep(std::time::Duration::from_millis(32 - item));
+            options.report(format_args!("checking {item}"));
+            options.report(format_args!("checked {item}"));
+            item * 2
+        });
+        assert_eq!(
+            results,
+            items.iter().map(|item| item * 2).collect::<Vec<_>>()
+        );
+        let expected = items
+            .iter()
+            .flat_map(|item| [format!("checking {item}"), format!("checked {item}")])
+            .collect::<Vec<_>>();
+        assert_eq!(*reporter.0.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn workspace_validation_error_from_errors_without_paths() {
+        let error = WorkspaceValidationError::from(io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(&error, WorkspaceValidationError::Io { path, .. } if path == ""));
+        assert!(error
+            .to_string()
+            .starts_with("Disk I/O Error reading `Cargo.toml` files:"));
+        let parse_error = "lints =".parse::<toml::Table>().unwrap_err();
+        let error = WorkspaceValidationError::from(parse_error);
+        assert!(error
+            .to_string()
+            .starts_with("Error parsing `Cargo.toml` files as TOML:"));
+    }
+
+    #[test]
+    fn is_policy_violation_for_each_variant() {
+        let path = || Utf8PathBuf::from("Cargo.toml");
+        let cases = [
+            (WorkspaceValidationError::FailingPackages(Vec::new()), true),
+            (
+                WorkspaceValidationError::WorkspaceLintsUndefined(path()),
+                true,
+            ),
+            (WorkspaceValidationError::NotAWorkspace(path()), true),
+            (
+                WorkspaceValidationError::RootLintsPolicy {
+                    path: path(),
+                    problems: Vec::new(),
+                },
+                true,
+            ),
+            (
+                WorkspaceValidationError::from(io::Error::from(io::ErrorKind::NotFound)),
+                false,
+            ),
+            (
+                WorkspaceValidationError::from("lints =".parse::<toml::Table>().unwrap_err()),
+                false,
+            ),
+            (
+                WorkspaceValidationError::from(cargo_metadata::Error::CargoMetadata {
+                    stderr: String::new(),
+                }),
+                false,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.is_policy_violation(), expected, "{error:?}");
+        }
+    }
+
+    #[test]
+    fn source_allows_reads_attributes() {
+        let workspace_lints: toml::Table = toml::from_str("rust.missing_docs = \"deny\"").unwrap();
+        let cases: &[(&str, &[&str])] = &[
+            ("#![allow(missing_docs, unused)]\n", &["missing_docs"]),
+            (
+                "#![allow(\n    clippy::pedantic,\n    missing_docs,\n)]\n",
+                &["clippy::pedantic", "missing_docs"],
+            ),
+            (
+                "#![expect(missing_docs, reason = \"a (b), c)\")]\n#![allow(warnings)]\n",
+                &["missing_docs", "warnings"],
+            ),
+            (
+                "#![cfg_attr(not(missing_docs), allow(warnings))]\n",
+                &["warnings"],
+            ),
+            (
+                "#![cfg_attr(\n    test,\n    allow(clippy::all),\n    deny(missing_docs),\n)]\n",
+                &["clippy::all"],
+            ),
+            (
+                "#![cfg_attr(missing_docs, doc = \"allow(warnings)\")]\n",
+                &[],
+            ),
+            ("#[allow(warnings)]\nfn main() {}\n", &[]),
+            (
+                "// #![allow(warnings)]\n/* /* */ #![allow(warnings)] */\n\
+                 const S: &str = r#\"#![allow(warnings)]\"#;\n",
+                &[],
+            ),
+            (
+                "fn f<'a>(c: char) -> bool { c == '[' }\n#![allow(warnings)]\n",
+                &["warnings"],
+            ),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(
+                advisory::source_allows(source, &workspace_lints),
+                *expected,
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn config_booleans_only_turn_options_on() {
+        let config: Config =
+            toml::from_str("deny-warnings = true\ninclude-unpublished = false\n").unwrap();
+        assert_eq!(config.deny_warnings, Some(true));
+        assert_eq!(config.include_unpublished, Some(false));
+        assert_eq!(config.check_rustflags, None);
+        let mut options = ValidationOptions {
+            include_unpublished: true,
+            ..ValidationOptions::default()
+        };
+        config.apply_defaults(&mut options);
+        assert!(options.deny_warnings);
+        assert!(options.include_unpublished);
+        assert!(!options.check_rustflags);
+    }
+
+    #[test]
+    fn unsorted_workspace_lints_reports_parse_errors_as_toml() {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "cargo-workspace-lints-unsorted-{}",
+                std::process::id()
+            ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[workspace.lints\n").unwrap();
+        let result = root::unsorted_workspace_lints(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        match result {
+            Err(WorkspaceValidationError::Toml { path, .. }) => {
+                assert_eq!(path, dir.join("Cargo.toml"));
+            }
+            other => panic!("expected a TOML error, got {other:?}"),
         }
     }
 }
@@ -1,8 +1,36 @@
 //! Parse a cargo workspace and check that all packages have `lints.workspace=true` set.
 
-use std::{path::PathBuf, process::ExitCode};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, IsTerminal, Write},
+    mem,
+    path::PathBuf,
+    process::ExitCode,
+    slice,
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use clap::Parser;
+use cargo_metadata::{
+    camino::{Utf8Path, Utf8PathBuf},
+    CargoOpt, Metadata, MetadataCommand,
+};
+use cargo_workspace_lints::{
+    format, json,
+    style::{self, paint, Color},
+    Baseline, Config, ConfiguredPackageResult, ExcludePattern, FalseFix, LintPolicy,
+    LocalLintPattern, NamedCheck, PackageResult, PackageStatus, PackageValidationErrorKind,
+    PlannedFix, Reporter, RequiredLint, Severity, SourceFiles, Stage, ValidationOptions,
+    WorkspaceReport, WorkspaceValidationError, CONFIG_FILE_NAME,
+};
+use clap::{
+    builder::{PathBufValueParser, TypedValueParser},
+    Args, CommandFactory, Parser, ValueEnum,
+};
 
 /// Parse a cargo workspace and check that all packages have `lints.workspace=true` set.
 #[derive(Parser)]
@@ -21,12 +49,70 @@ enum Command {
     WorkspaceLints(WorkspaceLintsArguments),
 }
 
-#[derive(clap::Args)]
+#[derive(Clone, clap::Args)]
+#[command(version, display_name = env!("CARGO_PKG_NAME"))]
+#[command(args_conflicts_with_subcommands = true)]
+#[command(after_help = "Exit status:
+  0  Every package passed.
+  1  Some package failed the check, or the workspace root failed one of the checks on it (such
+     as --require-lint, or an advisory check set to `deny`).
+  2  The command line was invalid, or `cargo metadata` failed.
+  3  A file couldn't be read, parsed, or written, or a program (such as `git` or `rustc`)
+     couldn't be run.
+
+Only 1 means the workspace itself fails the checks, so CI can retry the others as errors in
+running them.
+
+Config:
+  Defaults for the options which configure the checks, and for --format, can be given in
+  `.cargo-workspace-lints.toml` in the workspace root, or in `[workspace.metadata.workspace-lints]`
+  in the root manifest, with keys named like the options (such as `require = [...]` or
+  `no-local-lints = true`). Options given on the command line take precedence, but an option which
+  the config turns on (with `true`) can't be turned off on the command line.")]
+#[allow(clippy::struct_excessive_bools)]
 struct WorkspaceLintsArguments {
-    /// The path to the workspace you want to lint.
+    /// Something to do other than checking the workspace.
+    #[command(subcommand)]
+    action: Option<Action>,
+
+    /// The paths to the workspaces you want to lint.
+    ///
+    /// Each may be the manifest of the workspace root or of any member, or a directory, for which
+    /// the nearest `Cargo.toml` in it or its parents is used. The whole workspace is checked
+    /// either way. Defaults to the current working directory. If several are given, each is
+    /// checked and reported on in turn.
+    #[arg(conflicts_with = "members", value_parser = PathBufValueParser::new().map(find_manifest))]
+    manifest_path: Vec<PathBuf>,
+
+    /// Check only the given member manifests, without a workspace root.
+    ///
+    /// This doesn't run `cargo metadata`, so it works even if the workspace hasn't been assembled,
+    /// but any checks on the workspace root are skipped.
+    #[arg(long, value_name = "PATH", num_args = 1..)]
+    members: Vec<Utf8PathBuf>,
+
+    /// Quickly check only the package with the given manifest.
     ///
-    /// Defaults to the current working directory.
-    manifest_path: Option<PathBuf>,
+    /// This reads just that one manifest without running `cargo metadata`, which makes it suitable
+    /// for editor integrations. As with `--members`, the workspace root isn't known, so any checks
+    /// on it are skipped.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["manifest_path", "members", "check_all_features", "print_root_lints"]
+    )]
+    single: Option<Utf8PathBuf>,
+
+    /// Check every workspace under the given directory, each as its own workspace.
+    ///
+    /// Each workspace root found (any `Cargo.toml` with a `[workspace]` table, skipping `target`
+    /// and hidden directories) is checked and reported on in turn.
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["manifest_path", "members", "single", "fix_plan"]
+    )]
+    recursive: Option<Utf8PathBuf>,
 
     /// The path to the `cargo` executable to run.
     ///
@@ -37,39 +123,2352 @@ struct WorkspaceLintsArguments {
 
     /// Filter to only dependencies for the given target triple.
     ///
-    /// Defaults to not filtering dependencies.
+    /// May be repeated to check the packages surfaced for each target triple, which then include
+    /// the path dependencies outside the workspace resolved for it. Defaults to not filtering
+    /// dependencies.
+    #[arg(long, value_name = "TARGET_TRIPLE")]
+    filter_platform: Vec<String>,
+
+    /// Run `cargo metadata` without accessing the network.
+    #[arg(long)]
+    offline: bool,
+
+    /// Run `cargo metadata` requiring that `Cargo.lock` is up to date.
+    #[arg(long)]
+    locked: bool,
+
+    /// Run `cargo metadata` requiring that `Cargo.lock` is up to date, without accessing the
+    /// network.
+    #[arg(long)]
+    frozen: bool,
+
+    /// Features to enable when running `cargo metadata`, separated by commas or spaces.
+    ///
+    /// May be repeated.
+    #[arg(long, short = 'F', value_name = "FEATURES")]
+    features: Vec<String>,
+
+    /// Skip packages matching the given pattern.
+    ///
+    /// A pattern containing a `/` matches packages whose manifest is under that directory
+    /// (relative to the workspace root), a pattern containing `*` or `?` is a glob over package
+    /// names, and anything else must match a package name exactly. May be repeated.
+    ///
+    /// If neither this nor `--exclude-file` is given, the `exclude` list from
+    /// `.cargo-workspace-lints.toml` is used, if there is one. The patterns in
+    /// `workspace.metadata.workspace-lints.exclude` in the root manifest are always used.
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Read patterns to skip from the given file, one per line.
+    ///
+    /// Patterns have the same format as `--exclude`. Blank lines and anything following a `#`
+    /// are ignored. May be repeated.
+    #[arg(long, value_name = "PATH")]
+    exclude_file: Vec<PathBuf>,
+
+    /// Run the given command as an additional check on each package.
+    ///
+    /// The command is split on whitespace, and run with the path to the package's manifest as a
+    /// final argument. The package name, manifest path, and workspace root are also provided in
+    /// the `CARGO_WORKSPACE_LINTS_PACKAGE_NAME`, `CARGO_WORKSPACE_LINTS_MANIFEST_PATH`, and
+    /// `CARGO_WORKSPACE_LINTS_WORKSPACE_ROOT` environment variables. A package fails if the
+    /// command exits unsuccessfully, and then its output is written to stderr (as it is for every
+    /// run with `--verbose`, but never with `--quiet`). May be repeated.
+    #[arg(long, value_name = "CMD")]
+    hook: Vec<String>,
+
+    /// Accept packages whose `lints.workspace` field has this value, instead of `true`.
+    ///
+    /// `true` and `false` are matched as booleans; anything else is matched as a string. The field
+    /// must still be present. May be repeated.
+    #[arg(long, value_name = "VALUE")]
+    allow_value: Vec<String>,
+
+    /// Require the given dotted key, such as `package.edition.workspace`, to be `true` in every
+    /// package.
+    ///
+    /// Defaults to the `require` list from the workspace's config, or just `lints.workspace`.
+    /// Include it explicitly to check it alongside other keys. May be repeated.
+    #[arg(long, value_name = "KEY")]
+    require: Vec<String>,
+
+    /// Require every package to inherit the given field of `[package]`, such as `edition` or
+    /// `license`, with `field.workspace = true`.
+    ///
+    /// These are checked in addition to `lints.workspace`. Takes a comma-separated list, and may be
+    /// repeated.
+    #[arg(long, value_name = "FIELD", value_delimiter = ',')]
+    inherit: Vec<String>,
+
+    /// Also check packages which can't be published, which are skipped by default.
+    ///
+    /// This can also be set with `include-unpublished = true` in the workspace's config.
+    #[arg(long)]
+    include_unpublished: bool,
+
+    /// Fail packages which inherit the workspace lints but also define their own lints for some
+    /// tool, which would take precedence over the workspace's.
+    #[arg(long)]
+    no_local_lints: bool,
+
+    /// With `--no-local-lints`, accept packages setting the given lints locally anyway.
+    ///
+    /// The pattern has the form `[PACKAGE/]LINT`, where either part may be a glob, and lints from
+    /// tools other than `rustc` have the tool's prefix (such as `clippy::float_cmp`). So
+    /// `my-crate/*` accepts any local lints in `my-crate`. May be repeated.
+    ///
+    /// Defaults to the `allow-local-lints` list from the workspace's config.
+    #[arg(long, value_name = "PATTERN")]
+    allow_local_lint: Vec<String>,
+
+    /// Warn about packages whose own `[lints]` tables set a `priority` for some lint.
+    ///
+    /// These don't cause the package to fail, but may subvert the workspace lint policy.
+    #[arg(long)]
+    check_priority_overrides: bool,
+
+    /// Warn about packages which inherit the workspace lints but allow a broad lint group, such
+    /// as `warnings`, in their own `[lints]` tables.
+    ///
+    /// Some such overrides are deliberate, so these don't cause the package to fail.
+    #[arg(long)]
+    check_negating_overrides: bool,
+
+    /// Warn about packages whose sources turn off lints from `[workspace.lints]` with crate-level
+    /// `#![allow(...)]` or `#![expect(...)]` attributes.
+    ///
+    /// By default, only the root file of each library and binary target (such as `src/lib.rs`) is
+    /// scanned. Pass `=all` to scan every `.rs` file beside them too.
+    #[arg(
+        long,
+        value_name = "FILES",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "crate-roots"
+    )]
+    check_source_allows: Option<SourceFilesArg>,
+
+    /// Fail if the workspace root doesn't define any lints in `[workspace.lints]` for the packages
+    /// to inherit.
+    ///
+    /// By default, only the packages are checked, so that `lints.workspace = true` can be set
+    /// everywhere before the workspace has any lints.
+    #[arg(long, conflicts_with = "members")]
+    require_root_lints: bool,
+
+    /// Fail if the workspace root doesn't set the given lint to at least the given level in
+    /// `[workspace.lints]`, such as `unsafe_code=deny` or `clippy::unwrap_used=warn`.
+    ///
+    /// Only lints set by name count, not those set through a group such as `clippy::pedantic`. May
+    /// be repeated. Defaults to the `required-lints` table from the workspace's config.
+    #[arg(
+        long,
+        value_name = "LINT=LEVEL",
+        value_parser = parse_required_lint,
+        conflicts_with = "members"
+    )]
+    require_lint: Vec<RequiredLint>,
+
+    /// Fail if the workspace root sets any lint to `allow` in `[workspace.lints]`.
+    ///
+    /// This catches lints which were allowed for the whole workspace "temporarily" and never set
+    /// back. Lints which are meant to stay allowed can be listed with `--permit-allow`.
+    #[arg(long, conflicts_with = "members")]
+    deny_allow: bool,
+
+    /// With `--deny-allow`, let the workspace root allow lints matching the given glob, such as
+    /// `clippy::module_name_repetitions` or `clippy::*`.
+    ///
+    /// May be repeated. Defaults to the `permit-allow` list from the workspace's config.
+    #[arg(long, value_name = "LINT")]
+    permit_allow: Vec<String>,
+
+    /// Check the workspace root against the required lints and allows in the given policy file,
+    /// such as one shared between several repositories.
+    ///
+    /// The file may set `required-lints`, `deny-allow`, and `permit-allow`, as in the workspace's
+    /// config, and adds to what's set there. A policy file can also be given as `policy` in the
+    /// workspace's config, relative to its root, to apply both.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["members", "single"])]
+    policy: Option<Utf8PathBuf>,
+
+    /// Fail if the workspace root sets any lint in `[workspace.lints]` which isn't a known lint or
+    /// lint group, such as a misspelled one.
+    ///
+    /// The known lints are listed by running `rustc`, `clippy-driver`, and `rustdoc` with
+    /// `-W help`, so they must be on the `PATH` if the workspace sets lints for their tools.
+    #[arg(long, conflicts_with = "members")]
+    check_lint_names: bool,
+
+    /// Also run the given optional check on every package. May be repeated.
+    ///
+    /// Defaults to the `check` list from the workspace's config.
+    #[arg(long, value_enum, value_name = "CHECK", conflicts_with_all = ["members", "single"])]
+    check: Vec<CheckArg>,
+
+    /// Set how seriously to take the problems found by a check, as `CHECK=LEVEL` where the level
+    /// is `allow`, `warn`, or `deny`.
+    ///
+    /// The built-in check is named `lints`, hooks are named `hooks`, optional checks are named as
+    /// for `--check`, and advisory checks are named by the code in brackets on their warnings
+    /// (such as `priority-override`). At `warn`, problems are reported without affecting the exit
+    /// status. Checks which fail packages default to `deny`, and advisory checks to `warn`. May be
+    /// repeated, and defaults to the `severity` table from the workspace's config.
+    #[arg(long, value_name = "CHECK=LEVEL", value_parser = parse_severity)]
+    severity: Vec<(String, Severity)>,
+
+    /// Treat every check set to `warn` as `deny`, including advisory checks.
+    #[arg(long)]
+    deny_warnings: bool,
+
+    /// Skip workspace members whose manifests aren't inside the workspace root directory.
+    ///
+    /// By default, every workspace member is checked, wherever it is.
+    #[arg(long, conflicts_with = "members")]
+    workspace_root_only: bool,
+
+    /// Check a project which is a single package, rather than a workspace, for defining lints in
+    /// its own `[lints]` table.
+    ///
+    /// By default, such a project fails, since it has no `[workspace.lints]` to inherit.
+    #[arg(long, conflicts_with = "members")]
+    allow_single_package: bool,
+
+    /// Keep running, and check the workspace again whenever its root manifest, its config file, or
+    /// a member's manifest changes.
+    ///
+    /// The first check is reported in full. After that, only the packages whose results have
+    /// changed are printed, followed by the summary for the whole workspace. Only the manifests and
+    /// config files are watched, and they're polled for changes, so this works on any filesystem.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "recursive", "members", "single", "fix", "fix_plan", "write_baseline", "changed_only"
+        ]
+    )]
+    watch: bool,
+
+    /// Only check the packages whose manifests have changed, according to git.
+    ///
+    /// With a base ref (as in `--changed-only=origin/main`), these are the manifests changed since
+    /// the current branch diverged from it. Otherwise, they are the manifests with staged changes,
+    /// which suits pre-commit hooks. The checks on the workspace root's lints are only run if the
+    /// root manifest changed. A change to `.cargo-workspace-lints.toml` counts as a change to the
+    /// manifest beside it.
+    #[arg(
+        long,
+        value_name = "BASE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "",
+        conflicts_with_all = ["members", "single"]
+    )]
+    changed_only: Option<String>,
+
+    /// Warn if the lints in each tool table of the root `[workspace.lints]` aren't sorted
+    /// alphabetically.
+    #[arg(long, conflicts_with = "members")]
+    check_root_lints_sorted: bool,
+
+    /// Warn if a lint group in the root `[workspace.lints]` may override one of its lints set
+    /// to a different level there, because the group's `priority` isn't lower.
+    ///
+    /// The lints in each group are listed by running `rustc`, `clippy-driver`, and `rustdoc` with
+    /// `-W help`, as for `--check-lint-names`.
+    #[arg(long, conflicts_with = "members")]
+    check_lint_priorities: bool,
+
+    /// Warn if the root `[workspace.lints]` sets lints which have been renamed or removed, or sets
+    /// a lint more than once under different names (such as its old and new names).
+    #[arg(long, conflicts_with = "members")]
+    check_lint_consistency: bool,
+
+    /// Warn if the `rustflags` in the workspace's `.cargo/config.toml` files override lint levels
+    /// set in the root `[workspace.lints]`, such as with `-A clippy::all` or `--cap-lints`.
+    #[arg(long, conflicts_with = "members")]
+    check_rustflags: bool,
+
+    /// Warn about packages under the workspace root which aren't checked with it, because they
+    /// aren't members (such as those listed in `workspace.exclude`) or are in nested workspaces.
+    ///
+    /// Nested workspaces can be checked too with `--recursive`.
+    #[arg(long, conflicts_with = "members")]
+    check_escaped_packages: bool,
+
+    /// Report packages which define their own lints separately from those with no lints at all.
+    ///
+    /// These packages need their lints moved to the workspace root, rather than just inheriting
+    /// the workspace lints.
+    #[arg(long)]
+    detect_inline_lints: bool,
+
+    /// Print guidance on how to fix failing packages.
+    ///
+    /// The guidance is printed once for each kind of failure, alongside the first package which
+    /// fails that way. Packages which `--fix` could fix are also shown the change to their
+    /// manifests as a diff.
+    #[arg(long)]
+    suggest: bool,
+
+    /// List failing packages without blank lines between them.
+    #[arg(long)]
+    dense: bool,
+
+    /// List packages which set `lints.workspace = false` separately from those missing the field,
+    /// with a count of each.
+    ///
+    /// This only affects the human-readable report.
+    #[arg(long)]
+    deny_workspace_false: bool,
+
+    /// Print how many packages pass under each top-level directory of the workspace.
+    #[arg(long)]
+    by_dir_summary: bool,
+
+    /// Print how many packages pass, are missing `lints.workspace`, or have the wrong value, and
+    /// the percentage of checked packages which pass.
+    ///
+    /// Skipped packages are counted separately. The exit code still reflects whether every
+    /// package passed.
+    #[arg(long)]
+    stats: bool,
+
+    /// Also check the packages surfaced when all features are enabled.
+    ///
+    /// This runs `cargo metadata` both with the default features and with `--all-features`, and
+    /// checks every package surfaced by either. Every workspace member is surfaced either way, so
+    /// this also checks the path dependencies outside the workspace which each one resolves.
+    #[arg(long, conflicts_with = "members")]
+    check_all_features: bool,
+
+    /// Print the edits which would fix the failing packages as JSON, without making them.
+    #[arg(long)]
+    fix_plan: bool,
+
+    /// Edit the manifests of failing packages to make them pass, where possible.
+    ///
+    /// Comments and formatting elsewhere in each manifest are preserved. This succeeds only if
+    /// every failing package could be fixed.
+    #[arg(long, conflicts_with = "fix_plan")]
+    fix: bool,
+
+    /// With `--fix`, show the edit for each failing package and ask whether to make it.
+    ///
+    /// Each edit can be accepted, skipped (such as for a package which deliberately opts out), or
+    /// the rest abandoned. Answers are read from stdin.
+    #[arg(long, requires = "fix", conflicts_with = "quiet")]
+    interactive: bool,
+
+    /// How to fix packages which set `lints.workspace = false`.
+    #[arg(long, value_enum, value_name = "STRATEGY", default_value_t = FixFalse::SetTrue)]
+    fix_false: FixFalse,
+
+    /// Fail if fewer than this many packages were checked.
+    ///
+    /// Skipped packages don't count as checked. This guards against a misconfiguration silently
+    /// filtering out most of the workspace.
+    #[arg(long, value_name = "N")]
+    expect_min_packages: Option<usize>,
+
+    /// Record the packages which currently fail, and why, in the given file, to be passed to
+    /// `--baseline` later.
+    ///
+    /// This always succeeds if the file could be written.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["baseline", "fix", "fix_plan"])]
+    write_baseline: Option<Utf8PathBuf>,
+
+    /// Skip packages which fail in the same way as recorded in the given file by
+    /// `--write-baseline`.
+    ///
+    /// Packages which have started failing since, or fail for a different reason, still fail. This
+    /// lets a large workspace adopt the check gradually.
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<Utf8PathBuf>,
+
+    /// After checking, print the lints configured in the workspace root.
+    #[arg(long, conflicts_with = "members")]
+    print_root_lints: bool,
+
+    /// Append a Markdown summary of the results to the given file.
+    ///
+    /// Defaults to the file named by `GITHUB_STEP_SUMMARY`, if it is set, so that the results
+    /// appear on the summary page of a GitHub Actions run.
+    #[arg(long, value_name = "PATH")]
+    github_summary: Option<PathBuf>,
+
+    /// The format to write the report in.
+    ///
+    /// Defaults to the `format` given in the workspace's config. If there is none, defaults to
+    /// `github` when running in GitHub Actions (with `GITHUB_ACTIONS=true`), or `human` otherwise.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// When to color the output.
+    ///
+    /// With `auto`, output is colored if it is going to a terminal and `NO_COLOR` isn't set.
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, value_name = "WHEN")]
+    color: ColorChoice,
+
+    /// Write the report to stdout instead of stderr.
+    ///
+    /// Progress output from `--verbose` is still written to stderr.
     #[arg(long)]
-    filter_platform: Option<String>,
+    stdout: bool,
+
+    /// Print a header naming what was checked and a one-line summary around the report, and
+    /// wrap long lines, even when the report isn't going to a terminal.
+    ///
+    /// These are always printed to a terminal, unless color is turned off. Otherwise, the report
+    /// is left as it has always been, for scripts which parse it.
+    #[arg(long)]
+    decorate: bool,
 
     /// Get more verbose output.
     #[arg(short, long)]
     verbose: bool,
+
+    /// Don't print the report, only a one-line summary of how many packages passed, and
+    /// communicate mainly through the exit code.
+    ///
+    /// Machine-readable reports, such as with `--format json`, are still written to stdout. With
+    /// `--stdout`, the summary is left out of them.
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+impl WorkspaceLintsArguments {
+    /// The flags to pass through to `cargo metadata`.
+    fn cargo_options(&self) -> Vec<String> {
+        let flags = [
+            (self.offline, "--offline"),
+            (self.locked, "--locked"),
+            (self.frozen, "--frozen"),
+        ];
+        let mut options = flags
+            .into_iter()
+            .filter(|&(set, _)| set)
+            .map(|(_, flag)| flag.to_owned())
+            .collect::<Vec<_>>();
+        if !self.features.is_empty() {
+            options.push("--features".to_owned());
+            options.push(self.features.join(","));
+        }
+        options
+    }
+
+    /// The format to write the report in.
+    fn format(&self) -> Format {
+        self.format.unwrap_or_else(|| {
+            if env::var_os("GITHUB_ACTIONS").is_some_and(|value| value == "true") {
+                Format::Github
+            } else {
+                Format::Human
+            }
+        })
+    }
+}
+
+/// Things to do other than checking the workspace.
+#[derive(Clone, clap::Subcommand)]
+enum Action {
+    /// Print a shell completion script to stdout.
+    ///
+    /// The script completes the `cargo-workspace-lints` binary, which can be run directly with the
+    /// same arguments as `cargo workspace-lints`.
+    Completions {
+        /// The shell to generate completions for.
+        shell: clap_complete::Shell,
+    },
+    /// Print a manual page for `cargo workspace-lints` to stdout, in roff format.
+    ///
+    /// This can be installed as `cargo-workspace-lints.1`, to be read with
+    /// `man cargo-workspace-lints`.
+    Man,
+    /// Move the lints members define in their own `[lints]` tables into `[workspace.lints]` in the
+    /// workspace root, and set `lints.workspace = true` in every member.
+    ///
+    /// Nothing is changed if members set some lint to different values, and the conflicts are
+    /// listed instead, to be resolved by hand first.
+    Migrate {
+        /// The path to the workspace to migrate, as for checking.
+        ///
+        /// Defaults to the current working directory.
+        #[arg(value_parser = PathBufValueParser::new().map(find_manifest))]
+        manifest_path: Option<PathBuf>,
+
+        /// Print the changes which would be made, without making them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the lints each workspace member gets, with the level and priority of each and the
+    /// manifest which sets it.
+    ///
+    /// Members which set `lints.workspace = true` get the lints from `[workspace.lints]`, and any
+    /// of their own lints take precedence over those. The lints are ordered by priority, since
+    /// those with a higher priority take precedence.
+    List {
+        /// Only list the lints for the member with this name.
+        package: Option<String>,
+
+        /// The path to the workspace, as for checking.
+        ///
+        /// Defaults to the current working directory.
+        #[arg(long, value_parser = PathBufValueParser::new().map(find_manifest))]
+        manifest_path: Option<PathBuf>,
+    },
+}
+
+/// How to fix packages which set `lints.workspace = false`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum FixFalse {
+    /// Remove the key, so it is unambiguously not set.
+    ///
+    /// The package still fails the check afterwards, so `--fix` still fails.
+    Remove,
+    /// Set the key to `true`.
+    SetTrue,
+}
+
+impl From<FixFalse> for FalseFix {
+    fn from(fix_false: FixFalse) -> Self {
+        match fix_false {
+            FixFalse::Remove => Self::Remove,
+            FixFalse::SetTrue => Self::SetTrue,
+        }
+    }
+}
+
+/// The formats the report can be written in.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum Format {
+    /// A list of the failing packages.
+    Human,
+    /// A directory tree of the workspace, marking the status of each package.
+    Tree,
+    /// A JSON object listing the result for each package and any warnings, written to stdout.
+    ///
+    /// This can be read back as a `cargo_workspace_lints::json::Report`. With several workspaces
+    /// (from `--recursive` or several manifest paths), it's an array of one object for each.
+    Json,
+    /// GitHub Actions annotations on the manifests of the failing packages, written to stdout.
+    Github,
+    /// A SARIF log of the failing packages and any warnings, for code scanning tools, written to
+    /// stdout.
+    Sarif,
+    /// A JUnit XML report with a test case for each package, for CI test reports, written to
+    /// stdout.
+    Junit,
+    /// A unified diff of the edits which would fix the failing packages, as made by `--fix`,
+    /// written to stdout without changing any files.
+    ///
+    /// Paths are relative to the workspace root, so the patch can be applied from there with
+    /// `git apply`. `--fix-false` chooses how packages setting `lints.workspace = false` are fixed.
+    Patch,
+}
+
+impl Format {
+    /// Whether reports in this format are written as a single combined document when checking
+    /// several workspaces, since concatenating one per workspace wouldn't be valid.
+    fn is_combined(self) -> bool {
+        matches!(self, Self::Json | Self::Sarif | Self::Junit)
+    }
+}
+
+/// Write the reports kept by [`Output::combined`] to stdout, as a single document for each
+/// format.
+///
+/// JSON reports are written as an array, SARIF reports as a log with a run for each workspace, and
+/// `JUnit` reports with a test suite for each workspace.
+fn write_combined(combined: Vec<(Format, WorkspaceReport)>) -> io::Result<()> {
+    let (mut json_reports, mut sarif_reports, mut junit_reports) =
+        (Vec::new(), Vec::new(), Vec::new());
+    for (format, report) in combined {
+        match format {
+            Format::Json => json_reports.push(report),
+            Format::Sarif => sarif_reports.push(report),
+            Format::Junit => junit_reports.push(report),
+            Format::Human | Format::Tree | Format::Github | Format::Patch => {}
+        }
+    }
+    let mut stdout = io::stdout().lock();
+    if !json_reports.is_empty() {
+        let reports = json_reports
+            .iter()
+            .map(json::Report::new)
+            .collect::<Vec<_>>();
+        serde_json::to_writer_pretty(&mut stdout, &reports)?;
+        writeln!(stdout)?;
+    }
+    if !sarif_reports.is_empty() {
+        format::write_sarif_log(&mut stdout, &sarif_reports)?;
+    }
+    if !junit_reports.is_empty() {
+        format::write_junit_suites(&mut stdout, &junit_reports)?;
+    }
+    Ok(())
+}
+
+/// The manifest for a path to a workspace, finding the nearest one if it is a directory.
+///
+/// A directory without a manifest in it or its parents is left as it is, for `cargo metadata` to
+/// report.
+fn find_manifest(path: PathBuf) -> PathBuf {
+    if !path.is_dir() {
+        return path;
+    }
+    let absolute = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    absolute
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest_path| manifest_path.is_file())
+        .unwrap_or(path)
+}
+
+/// Parse the value of `--require-lint`.
+fn parse_required_lint(requirement: &str) -> Result<RequiredLint, String> {
+    RequiredLint::parse(requirement).ok_or_else(|| {
+        "expected `LINT=LEVEL`, where the level is `allow`, `warn`, `deny`, or `forbid`".to_owned()
+    })
+}
+
+/// Parse the value of `--severity`.
+fn parse_severity(setting: &str) -> Result<(String, Severity), String> {
+    setting
+        .split_once('=')
+        .and_then(|(check, level)| Some((check.to_owned(), Severity::parse(level)?)))
+        .ok_or_else(|| {
+            "expected `CHECK=LEVEL`, where the level is `allow`, `warn`, or `deny`".to_owned()
+        })
+}
+
+/// The optional checks for `--check`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum CheckArg {
+    /// Fail packages which declare dependencies themselves that the workspace root lists in
+    /// `[workspace.dependencies]`, instead of inheriting them with `workspace = true`.
+    WorkspaceDeps,
+}
+
+impl From<CheckArg> for NamedCheck {
+    fn from(check: CheckArg) -> Self {
+        match check {
+            CheckArg::WorkspaceDeps => Self::WorkspaceDeps,
+        }
+    }
+}
+
+/// Which source files `--check-source-allows` scans.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum SourceFilesArg {
+    /// The root file of each library and binary target.
+    CrateRoots,
+    /// Every `.rs` file in the directories of the crate roots.
+    All,
+}
+
+impl From<SourceFilesArg> for SourceFiles {
+    fn from(files: SourceFilesArg) -> Self {
+        match files {
+            SourceFilesArg::CrateRoots => Self::CrateRoots,
+            SourceFilesArg::All => Self::All,
+        }
+    }
+}
+
+/// When to color the output.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ColorChoice {
+    /// Color output going to a terminal, unless `NO_COLOR` is set.
+    Auto,
+    /// Always color the output.
+    Always,
+    /// Never color the output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output written to a stream, given whether that stream is a terminal.
+    fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            Self::Auto => {
+                is_terminal && !env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
 }
 
 fn main() -> ExitCode {
-    let args = Arguments::parse();
-    let Command::WorkspaceLints(args) = args.command;
+    let args = Arguments::parse_from(cargo_args(env::args_os()));
+    let Command::WorkspaceLints(mut args) = args.command;
+    if let Some(Action::Completions { shell }) = args.action {
+        // Completing `cargo` itself would replace cargo's own completions, so only this binary's
+        // are generated.
+        let name = env!("CARGO_PKG_NAME");
+        let mut command = WorkspaceLintsArguments::augment_args(clap::Command::new(name));
+        clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+        return ExitCode::SUCCESS;
+    }
+    if let Some(Action::Man) = args.action {
+        let written = write_man_page(&mut io::stdout().lock());
+        return written
+            .map_or(Outcome::FileError, |()| Outcome::Passed)
+            .into();
+    }
+    style::set_color(args.color.enabled(io::stderr().is_terminal()));
     let mut metadata_command = cargo_metadata::MetadataCommand::new();
-    metadata_command.no_deps().verbose(args.verbose);
-    if let Some(path) = args.manifest_path {
+    metadata_command.verbose(args.verbose);
+    if let [path] = &args.manifest_path[..] {
         metadata_command.manifest_path(path);
     }
-    if let Some(path) = args.cargo_path {
+    if let Some(path) = &args.cargo_path {
         metadata_command.cargo_path(path);
     }
-    if let Some(target_triple) = args.filter_platform {
-        metadata_command.other_options(&["--filter-platform".to_owned(), target_triple]);
+    metadata_command.other_options(args.cargo_options());
+    if let Some(Action::Migrate {
+        manifest_path,
+        dry_run,
+    }) = &args.action
+    {
+        if let Some(path) = manifest_path {
+            metadata_command.manifest_path(path);
+        }
+        return migrate(metadata_command.no_deps(), *dry_run).into();
+    }
+    if let Some(Action::List {
+        package,
+        manifest_path,
+    }) = &args.action
+    {
+        if let Some(path) = manifest_path {
+            metadata_command.manifest_path(path);
+        }
+        return list(metadata_command.no_deps(), package.as_deref()).into();
+    }
+    let Some(mut options) = validation_options(&args) else {
+        return Outcome::FileError.into();
+    };
+    let configurations = metadata_configurations(&metadata_command, &args);
+    if let Some(dir) = &args.recursive {
+        return match cargo_workspace_lints::find_workspace_roots(dir) {
+            Ok(manifest_paths) => {
+                check_workspaces(manifest_paths, &metadata_command, &options, &args).into()
+            }
+            Err(e) => {
+                eprintln!("Failed to find workspaces under {dir}:\n{e}");
+                Outcome::of_error(&e).into()
+            }
+        };
+    }
+    if args.watch {
+        return watch(&metadata_command, &options, &args).into();
+    }
+    if args.manifest_path.len() > 1 {
+        return check_workspaces(&args.manifest_path, &metadata_command, &options, &args).into();
+    }
+    let show_configurations = configurations.len() > 1;
+    let results = if let Some(manifest_path) = &args.single {
+        cargo_workspace_lints::check_member_manifests(
+            slice::from_ref(manifest_path),
+            &options,
+            args.verbose,
+        )
+        .map(member_report)
+    } else if args.members.is_empty() {
+        let Some(results) = check_configurations(&configurations, &mut options, &mut args) else {
+            return Outcome::FileError.into();
+        };
+        results
+    } else {
+        if !args.quiet {
+            eprintln!(
+                "Note: no workspace root was provided, so workspace root checks were skipped"
+            );
+        }
+        cargo_workspace_lints::check_member_manifests(&args.members, &options, args.verbose)
+            .map(member_report)
+    };
+    let mut output = Output::new(args.stdout, args.quiet, args.color, args.decorate);
+    // There's nowhere left to report a write error, so it's only told by the exit code.
+    report(&mut output, results, &args, show_configurations)
+        .unwrap_or(Outcome::FileError)
+        .into()
+}
+
+/// How a check ended, which determines the exit code.
+///
+/// These are ordered by severity, so that the most severe outcome of several checks can be used.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Outcome {
+    /// Every package passed.
+    Passed = 0,
+    /// Some package failed the check, or the workspace couldn't pass it for another reason.
+    Failed = 1,
+    /// `cargo metadata` failed.
+    ///
+    /// This is the same code clap exits with for an invalid command line.
+    MetadataError = 2,
+    /// A file couldn't be read, parsed, or written, or a program couldn't be run.
+    FileError = 3,
+}
+
+impl Outcome {
+    /// The outcome of a check which failed with the given error.
+    fn of_error(error: &WorkspaceValidationError) -> Self {
+        match error {
+            error if error.is_policy_violation() => Self::Failed,
+            WorkspaceValidationError::CargoMetadata(_) => Self::MetadataError,
+            _ => Self::FileError,
+        }
+    }
+}
+
+impl From<Outcome> for ExitCode {
+    fn from(outcome: Outcome) -> Self {
+        Self::from(outcome as u8)
+    }
+}
+
+/// The command-line arguments as cargo passes them to a subcommand.
+///
+/// Cargo runs `cargo workspace-lints ...` as `cargo-workspace-lints workspace-lints ...`, so when
+/// the binary is run directly (including with `cargo run`) without the `workspace-lints` word, it
+/// is inserted.
+fn cargo_args(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args = args.into_iter().collect::<Vec<_>>();
+    if args.get(1).map_or(true, |arg| arg != "workspace-lints") {
+        args.insert(args.len().min(1), "workspace-lints".into());
+    }
+    args
+}
+
+/// Build the validation options from the command-line arguments.
+///
+/// Returns `None`, having printed why, if an exclude file couldn't be read or the changed
+/// manifests couldn't be found.
+fn validation_options(args: &WorkspaceLintsArguments) -> Option<ValidationOptions> {
+    let mut options = ValidationOptions {
+        excludes: args
+            .exclude
+            .iter()
+            .map(|p| ExcludePattern::parse(p))
+            .collect(),
+        hooks: args.hook.clone(),
+        detect_inline_lints: args.detect_inline_lints,
+        check_priority_overrides: args.check_priority_overrides,
+        check_negating_overrides: args.check_negating_overrides,
+        check_root_lints_sorted: args.check_root_lints_sorted,
+        check_root_lint_priorities: args.check_lint_priorities,
+        check_root_lint_consistency: args.check_lint_consistency,
+        check_rustflags: args.check_rustflags,
+        check_escaped_packages: args.check_escaped_packages,
+        required_keys: args.require.clone(),
+        include_unpublished: args.include_unpublished,
+        reject_local_lints: args.no_local_lints,
+        allowed_local_lints: args
+            .allow_local_lint
+            .iter()
+            .map(|p| LocalLintPattern::parse(p))
+            .collect(),
+        require_root_lints: args.require_root_lints,
+        required_lints: args.require_lint.clone(),
+        reject_root_allows: args.deny_allow,
+        permitted_root_allows: args.permit_allow.clone(),
+        check_lint_names: args.check_lint_names,
+        checks: Vec::new(),
+        named_checks: args.check.iter().map(|&check| check.into()).collect(),
+        severities: args.severity.iter().cloned().collect(),
+        deny_warnings: args.deny_warnings,
+        changed_manifests: None,
+        verbose: args.verbose,
+        // Progress goes to stderr, keeping stdout for the report. In quiet mode, nothing does
+        // (not even the output of failing hooks), leaving just the summary.
+        reporter: if args.quiet {
+            Some(Arc::new(Mutex::new(io::sink())))
+        } else {
+            (io::stderr().is_terminal() && !args.verbose)
+                .then(|| Arc::new(ProgressBar) as Arc<dyn Reporter>)
+        },
+        workspace_root_only: args.workspace_root_only,
+        allow_single_package: args.allow_single_package,
+        inherited_fields: args.inherit.clone(),
+        check_source_allows: args.check_source_allows.map(SourceFiles::from),
+        allowed_values: args
+            .allow_value
+            .iter()
+            .map(|value| match value.parse() {
+                Ok(boolean) => toml::Value::Boolean(boolean),
+                Err(_) => toml::Value::String(value.clone()),
+            })
+            .collect(),
+    };
+    for path in &args.exclude_file {
+        match fs::read_to_string(path) {
+            Ok(contents) => options
+                .excludes
+                .extend(ExcludePattern::parse_file(&contents)),
+            Err(e) => {
+                eprintln!("Failed to read exclude file {}:\n    {e}", path.display());
+                return None;
+            }
+        }
     }
-    match cargo_workspace_lints::validate_workspace(&metadata_command, args.verbose) {
-        Ok(()) => {
-            if args.verbose {
-                eprintln!("All packages pass!");
+    if let Some(base) = &args.changed_only {
+        let dir = args.recursive.as_deref().or_else(|| {
+            let manifest_path = Utf8Path::from_path(args.manifest_path.first()?)?;
+            manifest_path
+                .parent()
+                .filter(|dir| !dir.as_str().is_empty())
+        });
+        match cargo_workspace_lints::changed_manifests(
+            dir.unwrap_or(Utf8Path::new(".")),
+            Some(base.as_str()).filter(|base| !base.is_empty()),
+        ) {
+            Ok(changed) => options.changed_manifests = Some(changed),
+            Err(e) => {
+                eprintln!("Failed to find the changed manifests:\n{e}");
+                return None;
             }
-            ExitCode::SUCCESS
         }
+    }
+    Some(options)
+}
+
+/// Check and report on each of the given workspaces in turn, returning the most severe outcome.
+///
+/// When there are several workspaces, a summary of how many passed follows their reports. Reports
+/// in formats which can't be concatenated, such as JSON, are combined into one document for every
+/// workspace, written at the end.
+fn check_workspaces(
+    manifest_paths: impl IntoIterator<Item = impl Into<PathBuf>>,
+    metadata_command: &MetadataCommand,
+    options: &ValidationOptions,
+    args: &WorkspaceLintsArguments,
+) -> Outcome {
+    let mut outcome = Outcome::Passed;
+    let mut checked = 0;
+    let mut passed = 0;
+    let mut combined = Vec::new();
+    for manifest_path in manifest_paths {
+        checked += 1;
+        let mut metadata_command = metadata_command.clone();
+        metadata_command.manifest_path(manifest_path);
+        let mut options = options.clone();
+        let mut args = args.clone();
+        let configurations = metadata_configurations(&metadata_command, &args);
+        let Some(results) = check_configurations(&configurations, &mut options, &mut args) else {
+            outcome = outcome.max(Outcome::FileError);
+            continue;
+        };
+        let mut output = Output::new(args.stdout, args.quiet, args.color, args.decorate);
+        output.combined = Some(mem::take(&mut combined));
+        let workspace_outcome = report(&mut output, results, &args, configurations.len() > 1)
+            .unwrap_or(Outcome::FileError);
+        combined = output.combined.take().unwrap_or_default();
+        if workspace_outcome == Outcome::Passed {
+            passed += 1;
+        }
+        outcome = outcome.max(workspace_outcome);
+    }
+    if checked > 1 && !args.quiet {
+        eprintln!(
+            "Checked {checked} workspaces: {passed} passed, {} failed",
+            checked - passed
+        );
+    }
+    if write_combined(combined).is_err() {
+        outcome = outcome.max(Outcome::FileError);
+    }
+    outcome
+}
+
+/// How often `--watch` polls the watched files for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Check the workspace again whenever one of its manifests or its config file changes, printing
+/// the packages whose results have changed since the previous check.
+///
+/// This only returns, with [`Outcome::FileError`], if the output couldn't be written.
+fn watch(
+    metadata_command: &MetadataCommand,
+    options: &ValidationOptions,
+    args: &WorkspaceLintsArguments,
+) -> Outcome {
+    if args.manifest_path.len() > 1 {
+        Arguments::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "`--watch` can only watch one workspace",
+            )
+            .exit();
+    }
+    // Until the workspace has been checked, only the manifest it was found from is known.
+    let mut watched = match &args.manifest_path[..] {
+        [path] => vec![path.clone()],
+        _ => vec![PathBuf::from("Cargo.toml")],
+    };
+    // The result for each package at the previous check, keyed by its manifest.
+    let mut previous = None::<BTreeMap<Utf8PathBuf, String>>;
+    loop {
+        // Files changed while checking should trigger the next check.
+        let before = modification_times(&watched);
+        let mut options = options.clone();
+        let mut args = args.clone();
+        let configurations = metadata_configurations(metadata_command, &args);
+        let results = check_configurations(&configurations, &mut options, &mut args);
+        // The output locks its stream, so it's only made once the check (which may draw the
+        // progress bar on that stream) has finished.
+        let mut output = Output::new(args.stdout, args.quiet, args.color, args.decorate);
+        let written = if let Some(results) = results {
+            let current = results.as_ref().ok().map(|workspace_report| {
+                watched = watched_files(workspace_report, &args);
+                package_results(workspace_report)
+            });
+            let written = match (results, previous.as_ref().zip(current.as_ref())) {
+                (Ok(workspace_report), Some((previous, current))) => {
+                    output.print_changes(previous, current, &workspace_report)
+                }
+                (results, _) => {
+                    report(&mut output, results, &args, configurations.len() > 1).map(|_| ())
+                }
+            };
+            if current.is_some() {
+                previous = current;
+            }
+            written
+        } else {
+            Ok(())
+        };
+        if written.is_err() {
+            return Outcome::FileError;
+        }
+        // Files found by the check are compared against when they were first seen, which must be
+        // before saying that they're watched, so that a change made straight after isn't missed.
+        let mut before = before;
+        for (path, modified) in modification_times(&watched) {
+            before.entry(path).or_insert(modified);
+        }
+        drop(output);
+        if !args.quiet {
+            eprintln!("Watching {} files for changes...", watched.len());
+        }
+        wait_for_change(&watched, &before);
+    }
+}
+
+/// A line describing the result for each package in the report, keyed by its manifest, as
+/// printed by `--watch`.
+fn package_results(report: &WorkspaceReport) -> BTreeMap<Utf8PathBuf, String> {
+    report
+        .packages
+        .iter()
+        .map(|result| {
+            let PackageResult {
+                name,
+                manifest_path,
+                status,
+                ..
+            } = &result.result;
+            let line = match status {
+                PackageStatus::Passed => {
+                    format!("PASS: Package {name} ({manifest_path})")
+                }
+                PackageStatus::Failed(kind) => {
+                    format!("FAIL: Package {name} ({manifest_path}): {kind}")
+                }
+                PackageStatus::Skipped(reason) => {
+                    format!("SKIP: Package {name} ({manifest_path}) {reason}")
+                }
+            };
+            (manifest_path.clone(), line)
+        })
+        .collect()
+}
+
+/// The files which `--watch` checks the workspace again after changes to.
+///
+/// These are only the manifests and config files: the root manifest, the config file and cargo
+/// config in the workspace root, any policy files, and each member's manifest. Source files aren't
+/// polled, however many there are, so changes to the attributes found by
+/// `--check-source-allows` are only picked up with the next change to one of these.
+fn watched_files(report: &WorkspaceReport, args: &WorkspaceLintsArguments) -> Vec<PathBuf> {
+    let root_files = report.workspace_root.iter().flat_map(|workspace_root| {
+        let config_policy = Config::load(workspace_root)
+            .ok()
+            .flatten()
+            .and_then(|config| config.policy)
+            .map(|path| workspace_root.join(path));
+        [
+            workspace_root.join("Cargo.toml"),
+            workspace_root.join(CONFIG_FILE_NAME),
+            workspace_root.join(".cargo/config"),
+            workspace_root.join(".cargo/config.toml"),
+        ]
+        .into_iter()
+        .chain(config_policy)
+    });
+    let mut watched = Vec::new();
+    for path in root_files.chain(args.policy.clone()).chain(
+        report
+            .packages
+            .iter()
+            .map(|result| result.result.manifest_path.clone()),
+    ) {
+        let path = PathBuf::from(path);
+        if !watched.contains(&path) {
+            watched.push(path);
+        }
+    }
+    watched
+}
+
+/// When each of the files was last modified, or `None` for those which don't exist.
+fn modification_times(paths: &[PathBuf]) -> HashMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+            (path.clone(), modified.ok())
+        })
+        .collect()
+}
+
+/// Wait until one of the files has been modified, created, or removed since the times in `before`,
+/// which has a time for each of them.
+fn wait_for_change(paths: &[PathBuf], before: &HashMap<PathBuf, Option<SystemTime>>) {
+    while modification_times(paths)
+        .iter()
+        .all(|(path, modified)| before[path] == *modified)
+    {
+        thread::sleep(WATCH_INTERVAL);
+    }
+    // Editors may save a file in several steps, so let them finish before checking it.
+    thread::sleep(WATCH_INTERVAL);
+}
+
+/// Run `cargo metadata` once for each configuration, then check the workspace with its config
+/// applied to `options` and `args`, as by [`apply_config`].
+///
+/// The config is found from the metadata for the first configuration, so that `cargo metadata`
+/// isn't run again just to find the workspace root. Returns `None`, having printed why, if the
+/// config couldn't be read.
+fn check_configurations(
+    configurations: &[(String, MetadataCommand)],
+    options: &mut ValidationOptions,
+    args: &mut WorkspaceLintsArguments,
+) -> Option<Result<WorkspaceReport, WorkspaceValidationError>> {
+    let mut metadata = Vec::new();
+    for (label, metadata_command) in configurations {
+        if let Some(reporter) = &options.reporter {
+            reporter.stage(Stage::Metadata);
+        }
+        match metadata_command.exec() {
+            Ok(configuration_metadata) => metadata.push((label.clone(), configuration_metadata)),
+            Err(e) => return Some(Err(e.into())),
+        }
+    }
+    if let Some((_, first)) = metadata.first() {
+        if !apply_config(first, options, &mut args.format, args.policy.as_deref()) {
+            return None;
+        }
+    }
+    Some(cargo_workspace_lints::check_metadata_configurations(
+        metadata,
+        options,
+        args.verbose,
+    ))
+}
+
+/// Fill in any options and the format not given on the command line from the config of the
+/// workspace described by `metadata`.
+///
+/// The config file in the workspace root takes precedence over `workspace.metadata.workspace-lints`
+/// in the root manifest. Then `policy` (from `--policy`) and any policy file named in either
+/// config are added to the options. Returns `false`, having printed why, if any of the files
+/// couldn't be read.
+fn apply_config(
+    metadata: &Metadata,
+    options: &mut ValidationOptions,
+    format: &mut Option<Format>,
+    policy: Option<&Utf8Path>,
+) -> bool {
+    let file_config = match Config::load(&metadata.workspace_root) {
+        Ok(config) => config,
         Err(e) => {
-            eprint!("Failed to validate:\n{e}");
-            ExitCode::FAILURE
+            eprintln!(
+                "Failed to read {}:\n    {e}",
+                metadata.workspace_root.join(CONFIG_FILE_NAME)
+            );
+            return false;
         }
+    };
+    let manifest_config = match Config::from_workspace_metadata(&metadata.workspace_metadata) {
+        Ok(config) => config.map(|config| Config {
+            // Checking the workspace already uses these excludes, alongside any others.
+            exclude: Vec::new(),
+            ..config
+        }),
+        Err(e) => {
+            eprintln!(
+                "Failed to read `workspace.metadata.workspace-lints` in {}:\n    {e}",
+                metadata.workspace_root.join("Cargo.toml")
+            );
+            return false;
+        }
+    };
+    let mut policies = policy
+        .map(Utf8Path::to_owned)
+        .into_iter()
+        .collect::<Vec<_>>();
+    for mut config in file_config.into_iter().chain(manifest_config) {
+        policies.extend(
+            config
+                .policy
+                .take()
+                .map(|path| metadata.workspace_root.join(path)),
+        );
+        if let Some(name) = config.format.as_deref().filter(|_| format.is_none()) {
+            match Format::from_str(name, true) {
+                Ok(config_format) => *format = Some(config_format),
+                Err(e) => {
+                    eprintln!("Invalid format in the workspace's config:\n    {e}");
+                    return false;
+                }
+            }
+        }
+        config.apply_defaults(options);
+    }
+    // Policies add to the options, so they come after the defaults which are only filled in.
+    for path in policies {
+        match LintPolicy::load(&path) {
+            Ok(policy) => policy.apply(options),
+            Err(e) => {
+                eprintln!("Failed to read policy file {path}:\n    {e}");
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Wrap the results of checking member manifests, which have no workspace root or configurations.
+fn member_report(results: Vec<PackageResult>) -> WorkspaceReport {
+    WorkspaceReport {
+        workspace_root: None,
+        packages: results
+            .into_iter()
+            .map(|result| ConfiguredPackageResult {
+                result,
+                configurations: Vec::new(),
+            })
+            .collect(),
+        warnings: Vec::new(),
+    }
+}
+
+/// Report the results of the check, returning its outcome.
+fn report(
+    output: &mut Output,
+    report: Result<WorkspaceReport, WorkspaceValidationError>,
+    args: &WorkspaceLintsArguments,
+    show_configurations: bool,
+) -> io::Result<Outcome> {
+    let mut report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            write!(output.stream, "Failed to validate:\n{e}")?;
+            if let WorkspaceValidationError::NotAWorkspace(_) = e {
+                writeln!(
+                    output.stream,
+                    "Pass `--allow-single-package` to check the package's own `[lints]` instead"
+                )?;
+            }
+            return Ok(Outcome::of_error(&e));
+        }
+    };
+    if let Some(outcome) = use_baseline(output, &mut report, args)? {
+        return Ok(outcome);
+    }
+    let false_fix = args.fix_false.into();
+    if args.fix {
+        return Ok(
+            if output.apply_fixes(&report, false_fix, args.interactive)? {
+                Outcome::Passed
+            } else {
+                Outcome::Failed
+            },
+        );
+    }
+    if args.fix_plan {
+        let plan = cargo_workspace_lints::plan_fixes(
+            report.packages.iter().map(|result| &result.result),
+            false_fix,
+        );
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&plan).expect("Fix plan serializes to JSON")
+        );
+        return Ok(Outcome::Passed);
+    }
+    let mut outcome = if output.print_results(&report, args, show_configurations)? {
+        Outcome::Passed
+    } else {
+        Outcome::Failed
+    };
+    let results = &report.packages;
+    if args.by_dir_summary {
+        format::write_dir_summary(&mut output.stream, &report)?;
+    }
+    if args.stats {
+        format::write_stats(&mut output.stream, &report)?;
+    }
+    if let Some(expected) = args.expect_min_packages {
+        let checked = results
+            .iter()
+            .filter(|result| !matches!(result.result.status, PackageStatus::Skipped(_)))
+            .count();
+        if checked < expected {
+            writeln!(
+                output.stream,
+                "Expected to check at least {expected} packages, but only checked {checked}"
+            )?;
+            outcome = outcome.max(Outcome::Failed);
+        }
+    }
+    if let Some(workspace_root) = report
+        .workspace_root
+        .as_deref()
+        .filter(|_| args.print_root_lints)
+    {
+        match cargo_workspace_lints::read_workspace_lints(workspace_root) {
+            Ok(lints) => output.print_root_lints(workspace_root, lints)?,
+            Err(e) => {
+                write!(output.stream, "Failed to read workspace lints:\n{e}")?;
+                outcome = outcome.max(Outcome::of_error(&e));
+            }
+        }
+    }
+    if !write_github_summary(output, &report, args)? {
+        outcome = outcome.max(Outcome::FileError);
+    }
+    // Don't mix the summary into a machine-readable report on stdout.
+    if !args.stdout || matches!(args.format(), Format::Human | Format::Tree) {
+        if args.quiet {
+            // The summary is the one thing still printed in quiet mode.
+            Output::new(args.stdout, false, args.color, true).print_summary_bar(results)?;
+        } else {
+            output.print_summary_bar(results)?;
+        }
+    }
+    if let Some(combined) = output
+        .combined
+        .as_mut()
+        .filter(|_| args.format().is_combined())
+    {
+        combined.push((args.format(), report));
+    }
+    Ok(outcome)
+}
+
+/// Append a Markdown summary of the report to the file from `--github-summary` or
+/// `$GITHUB_STEP_SUMMARY`, if either is set.
+///
+/// Returns `false`, having printed why, if the summary couldn't be written.
+fn write_github_summary(
+    output: &mut Output,
+    report: &WorkspaceReport,
+    args: &WorkspaceLintsArguments,
+) -> io::Result<bool> {
+    let Some(path) = args
+        .github_summary
+        .clone()
+        .or_else(|| env::var_os("GITHUB_STEP_SUMMARY").map(PathBuf::from))
+    else {
+        return Ok(true);
+    };
+    let written = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| format::write_markdown_summary(&mut file, report));
+    if let Err(e) = written {
+        writeln!(
+            output.stream,
+            "Failed to write summary to {}:\n    {e}",
+            path.display()
+        )?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Write the baseline for `--write-baseline`, or skip the known failures for `--baseline`.
+///
+/// Returns the outcome if there is nothing more to report, because the baseline was written or
+/// couldn't be read.
+fn use_baseline(
+    output: &mut Output,
+    report: &mut WorkspaceReport,
+    args: &WorkspaceLintsArguments,
+) -> io::Result<Option<Outcome>> {
+    if let Some(path) = &args.write_baseline {
+        let baseline = Baseline::new(report);
+        return Ok(Some(match baseline.write(path) {
+            Ok(()) => {
+                writeln!(
+                    output.stream,
+                    "Wrote {} known failures to {path}",
+                    baseline.failures.len()
+                )?;
+                Outcome::Passed
+            }
+            Err(e) => {
+                writeln!(
+                    output.stream,
+                    "Failed to write baseline to {path}:\n    {e}"
+                )?;
+                Outcome::FileError
+            }
+        }));
+    }
+    if let Some(path) = &args.baseline {
+        match Baseline::load(path) {
+            Ok(baseline) => {
+                let suppressed = baseline.apply(report);
+                if suppressed > 0 && !args.quiet {
+                    eprintln!("Note: skipped {suppressed} packages with known failures in {path}");
+                }
+            }
+            Err(e) => {
+                writeln!(output.stream, "Failed to read baseline {path}:\n    {e}")?;
+                return Ok(Some(Outcome::FileError));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Move the members' own lints into the workspace root, printing what is (or, with `dry_run`,
+/// would be) changed.
+fn migrate(metadata_command: &MetadataCommand, dry_run: bool) -> Outcome {
+    let migration = match metadata_command
+        .exec()
+        .map_err(WorkspaceValidationError::from)
+        .and_then(|metadata| cargo_workspace_lints::plan_migration(&metadata))
+    {
+        Ok(migration) => migration,
+        Err(e) => {
+            eprintln!("Failed to plan the migration:\n{e}");
+            return Outcome::of_error(&e);
+        }
+    };
+    if !migration.conflicts.is_empty() {
+        println!("Members set these lints differently, so nothing was changed:");
+        for conflict in &migration.conflicts {
+            println!("* `{}`:", conflict.lint);
+            for (package, value) in &conflict.values {
+                println!("    {package}: {value}");
+            }
+        }
+        return Outcome::Failed;
+    }
+    if migration.members.is_empty() {
+        println!("Every member already inherits the workspace lints");
+        return Outcome::Passed;
+    }
+    let verb = if dry_run { "Would add" } else { "Adding" };
+    println!(
+        "{verb} these lints to `[workspace.lints]` in {}:",
+        migration.root_manifest_path
+    );
+    for (tool, lints) in &migration.lints {
+        for (lint, value) in lints.as_table().into_iter().flatten() {
+            println!("    workspace.lints.{tool}.{lint} = {value}");
+        }
+    }
+    let verb = if dry_run { "Would set" } else { "Setting" };
+    println!("{verb} `lints.workspace = true` in:");
+    for path in &migration.members {
+        println!("    {path}");
+    }
+    if dry_run {
+        return Outcome::Passed;
+    }
+    match cargo_workspace_lints::apply_migration(&migration) {
+        Ok(()) => Outcome::Passed,
+        Err(e) => {
+            eprintln!("Failed to migrate:\n{e}");
+            Outcome::of_error(&e)
+        }
+    }
+}
+
+/// Print the lints each workspace member gets, or just the one named `package`.
+fn list(metadata_command: &MetadataCommand, package: Option<&str>) -> Outcome {
+    let packages = match metadata_command
+        .exec()
+        .map_err(WorkspaceValidationError::from)
+        .and_then(|metadata| cargo_workspace_lints::package_lints(&metadata))
+    {
+        Ok(packages) => packages,
+        Err(e) => {
+            eprintln!("Failed to resolve the lints:\n{e}");
+            return Outcome::of_error(&e);
+        }
+    };
+    let mut found = false;
+    for lints in packages
+        .iter()
+        .filter(|lints| package.map_or(true, |package| lints.name == package))
+    {
+        found = true;
+        let inherits = if lints.inherits {
+            ", inheriting the workspace lints"
+        } else {
+            ""
+        };
+        println!("{} ({}){inherits}:", lints.name, lints.manifest_path);
+        if lints.lints.is_empty() {
+            println!("    no lints");
+        }
+        let width = lints.lints.iter().map(|lint| lint.lint.len()).max();
+        for lint in &lints.lints {
+            println!(
+                "    {:width$}  {:6}  priority {:<3}  from {}",
+                lint.lint,
+                lint.level,
+                lint.priority,
+                lint.source,
+                width = width.unwrap_or_default(),
+            );
+        }
+    }
+    if let (Some(package), false) = (package, found) {
+        eprintln!("No workspace member is named `{package}`");
+        return Outcome::Failed;
+    }
+    Outcome::Passed
+}
+
+/// Build the labelled metadata commands for each configuration to check.
+///
+/// There is one configuration for each `--filter-platform` target triple (or just one, if there
+/// are none), with a second all-features configuration for each with `--check-all-features`.
+///
+/// Features and platforms only change which dependencies are resolved, so these are only resolved
+/// when there are several configurations to compare. A single configuration (even one filtered to
+/// a platform) is run with `--no-deps`, and so checks just the workspace members.
+fn metadata_configurations(
+    metadata_command: &MetadataCommand,
+    args: &WorkspaceLintsArguments,
+) -> Vec<(String, MetadataCommand)> {
+    let target_triples = &args.filter_platform;
+    let all_features = args.check_all_features;
+    let platforms = if target_triples.is_empty() {
+        vec![None]
+    } else {
+        target_triples.iter().map(Some).collect()
+    };
+    let resolve_dependencies = all_features || platforms.len() > 1;
+    let mut configurations = Vec::new();
+    for platform in platforms {
+        let mut command = metadata_command.clone();
+        if !resolve_dependencies {
+            command.no_deps();
+        }
+        if let Some(target_triple) = platform {
+            let mut options = args.cargo_options();
+            options.extend(["--filter-platform".to_owned(), target_triple.clone()]);
+            command.other_options(options);
+        }
+        let label = |features: &str| match platform {
+            Some(target_triple) if all_features => format!("{target_triple} with {features}"),
+            Some(target_triple) => target_triple.clone(),
+            None => features.to_owned(),
+        };
+        configurations.push((label("default features"), command.clone()));
+        if all_features {
+            command.features(CargoOpt::AllFeatures);
+            configurations.push((label("all features"), command));
+        }
+    }
+    configurations
+}
+
+/// The width to wrap output to, if it isn't going to a terminal.
+const DEFAULT_WIDTH: usize = 80;
+
+/// The widest the [`ProgressBar`] is drawn, not counting the text beside it.
+const PROGRESS_BAR_WIDTH: usize = 40;
+
+/// What the [`ProgressBar`] last drew, if it's still shown.
+static PROGRESS_DRAWN: Mutex<Option<Stage>> = Mutex::new(None);
+
+/// Shows how far the check has got on a single line of stderr, for when it's a terminal and
+/// progress isn't being reported verbosely.
+///
+/// The line is redrawn as each package is checked, and cleared once they all are. It's also
+/// cleared by [`ProgressBar::clear`] before the report is written, in case the check failed.
+#[derive(Debug)]
+struct ProgressBar;
+
+impl ProgressBar {
+    /// Clear the progress bar from stderr, if it's shown.
+    fn clear() {
+        let mut drawn = PROGRESS_DRAWN
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if drawn.take().is_some() {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+impl Reporter for ProgressBar {
+    fn report(&self, line: &str) {
+        Self::clear();
+        eprintln!("{line}");
+    }
+
+    fn stage(&self, stage: Stage) {
+        let mut drawn = PROGRESS_DRAWN
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let line = match stage {
+            Stage::Metadata => "Running `cargo metadata`...".to_owned(),
+            Stage::Packages { checked, total } if checked < total => {
+                // Packages are checked in parallel, so a later count may already be shown.
+                if matches!(*drawn, Some(Stage::Packages { checked: shown, .. }) if shown > checked)
+                {
+                    return;
+                }
+                let count = format!(" {checked}/{total} packages");
+                let width = terminal_size::terminal_size_of(io::stderr())
+                    .map_or(DEFAULT_WIDTH, |(terminal_size::Width(width), _)| {
+                        usize::from(width)
+                    })
+                    .saturating_sub(count.len() + 3)
+                    .min(PROGRESS_BAR_WIDTH);
+                let filled = width * checked / total;
+                format!(
+                    "[{}{}]{count}",
+                    "=".repeat(filled),
+                    " ".repeat(width - filled)
+                )
+            }
+            _ => {
+                if drawn.take().is_some() {
+                    eprint!("\r\x1b[2K");
+                }
+                return;
+            }
+        };
+        eprint!("\r\x1b[2K{line}");
+        *drawn = Some(stage);
+    }
+}
+
+/// Where the human-readable report is written.
+struct Output {
+    /// The stream to write to.
+    stream: Box<dyn Write>,
+    /// Whether to color the output.
+    color: bool,
+    /// The width to wrap output to.
+    width: usize,
+    /// Whether to print the header and summary lines around the report.
+    decorated: bool,
+    /// Reports in formats which can't be concatenated (such as JSON), kept to write as one
+    /// combined document once every workspace has been checked, rather than being written now.
+    combined: Option<Vec<(Format, WorkspaceReport)>>,
+}
+
+impl Output {
+    /// Write to stdout if `stdout` is set, or stderr otherwise, or nowhere at all if `quiet` is
+    /// set.
+    ///
+    /// Unless the stream is a colored terminal or `decorate` is set, the output is kept as plain as
+    /// it's always been: no header or summary lines, and no wrapping. So turning color off (such as
+    /// with `NO_COLOR`) gives exactly the old output, even on a terminal.
+    fn new(stdout: bool, quiet: bool, color: ColorChoice, decorate: bool) -> Self {
+        ProgressBar::clear();
+        let (stream, is_terminal, size): (Box<dyn Write>, _, _) = if quiet {
+            (Box::new(io::sink()), false, None)
+        } else if stdout {
+            let stream = io::stdout();
+            (
+                Box::new(stream.lock()),
+                stream.is_terminal(),
+                terminal_size::terminal_size_of(stream),
+            )
+        } else {
+            let stream = io::stderr();
+            (
+                Box::new(stream.lock()),
+                stream.is_terminal(),
+                terminal_size::terminal_size_of(stream),
+            )
+        };
+        let color = color.enabled(is_terminal);
+        let decorated = (is_terminal && color) || decorate;
+        Self {
+            stream,
+            color,
+            width: match size {
+                Some((terminal_size::Width(width), _)) if decorated => usize::from(width),
+                _ if decorated => DEFAULT_WIDTH,
+                _ => usize::MAX,
+            },
+            decorated,
+            combined: None,
+        }
+    }
+
+    /// Print a one-line count of passed, failed, and skipped packages.
+    ///
+    /// If the output is colored, the counts are marked with glyphs, and otherwise are written out
+    /// as a sentence for logs. This is left out of undecorated output.
+    fn print_summary_bar(&mut self, results: &[ConfiguredPackageResult]) -> io::Result<()> {
+        if !self.decorated {
+            return Ok(());
+        }
+        let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+        for result in results {
+            match result.result.status {
+                PackageStatus::Passed => passed += 1,
+                PackageStatus::Failed(_) => failed += 1,
+                PackageStatus::Skipped(_) => skipped += 1,
+            }
+        }
+        if self.color {
+            writeln!(
+                self.stream,
+                "{}  {}  {}",
+                paint(format_args!("✔ {passed} passed"), Color::Green, true),
+                paint(format_args!("✘ {failed} failed"), Color::Red, true),
+                paint(format_args!("⊘ {skipped} skipped"), Color::Yellow, true),
+            )
+        } else {
+            let checked = passed + failed;
+            let noun = if checked == 1 { "package" } else { "packages" };
+            writeln!(
+                self.stream,
+                "{checked} {noun} checked, {passed} passing, {failed} failing, {skipped} skipped"
+            )
+        }
+    }
+
+    /// Print the result for each package which has changed since the previous check (as given by
+    /// `package_results`) for `--watch`, followed by the summary.
+    fn print_changes(
+        &mut self,
+        previous: &BTreeMap<Utf8PathBuf, String>,
+        current: &BTreeMap<Utf8PathBuf, String>,
+        report: &WorkspaceReport,
+    ) -> io::Result<()> {
+        for (manifest_path, line) in current {
+            if previous.get(manifest_path) != Some(line) {
+                writeln!(self.stream, "{line}")?;
+            }
+        }
+        for manifest_path in previous.keys() {
+            if !current.contains_key(manifest_path) {
+                writeln!(self.stream, "Package {manifest_path} is no longer checked")?;
+            }
+        }
+        self.print_warnings(report)?;
+        self.print_summary_bar(&report.packages)
+    }
+
+    /// Fix every failing package that can be fixed, reporting which manifests were modified.
+    ///
+    /// If `interactive` is set, each edit is shown first, and only made if accepted on stdin.
+    /// Returns whether every failing package was fixed.
+    fn apply_fixes(
+        &mut self,
+        report: &WorkspaceReport,
+        false_fix: FalseFix,
+        interactive: bool,
+    ) -> io::Result<bool> {
+        let failing = report
+            .packages
+            .iter()
+            .filter(|result| matches!(result.result.status, PackageStatus::Failed(_)))
+            .count();
+        let plan = cargo_workspace_lints::plan_fixes(
+            report.packages.iter().map(|result| &result.result),
+            false_fix,
+        );
+        let mut fixed = 0;
+        for fix in &plan {
+            // Don't ask about edits which can't be made anyway.
+            if let (true, Err(e)) = (interactive, cargo_workspace_lints::fixed_manifest(fix)) {
+                writeln!(self.stream, "Failed to fix {}:\n    {e}", fix.manifest_path)?;
+                continue;
+            }
+            if interactive {
+                writeln!(self.stream, "* Package {}:", fix.package)?;
+                self.print_fix_diff(fix)?;
+                match self.prompt("Make this edit? [y]es, [s]kip, or [a]bort")? {
+                    'y' => {}
+                    's' => continue,
+                    _ => break,
+                }
+            }
+            match cargo_workspace_lints::apply_fix(fix) {
+                Ok(()) if fix.edit.makes_package_pass() => {
+                    writeln!(self.stream, "Fixed {}", fix.manifest_path)?;
+                    fixed += 1;
+                }
+                Ok(()) => writeln!(
+                    self.stream,
+                    "Removed `lints.workspace` from {}, which still fails the check",
+                    fix.manifest_path
+                )?,
+                Err(e) => writeln!(self.stream, "Failed to fix {}:\n    {e}", fix.manifest_path)?,
+            }
+        }
+        writeln!(self.stream, "Fixed {fixed} of {failing} failing packages")?;
+        Ok(fixed == failing)
+    }
+
+    /// Print the results in the requested format, returning whether every package passed.
+    fn print_results(
+        &mut self,
+        report: &WorkspaceReport,
+        args: &WorkspaceLintsArguments,
+        show_configurations: bool,
+    ) -> io::Result<bool> {
+        let any_failed = report
+            .packages
+            .iter()
+            .any(|result| matches!(result.result.status, PackageStatus::Failed(_)));
+        match args.format() {
+            format if format.is_combined() && self.combined.is_some() => {}
+            Format::Tree => format::write_tree(&mut self.stream, report)?,
+            Format::Json => writeln!(
+                io::stdout(),
+                "{}",
+                serde_json::to_string_pretty(&json::Report::new(report))
+                    .expect("Report serializes to JSON")
+            )?,
+            Format::Github => format::write_github_annotations(&mut io::stdout(), report)?,
+            Format::Sarif => format::write_sarif(&mut io::stdout(), report)?,
+            Format::Junit => format::write_junit(&mut io::stdout(), report)?,
+            Format::Patch => {
+                let outside =
+                    format::write_patch(&mut io::stdout(), report, args.fix_false.into())?;
+                for manifest_path in outside {
+                    eprintln!(
+                        "Note: left the fix for {manifest_path} out of the patch, since `git \
+                         apply` can't apply it outside the directory it's run in"
+                    );
+                }
+            }
+            Format::Human => {
+                self.print_header(report)?;
+                if any_failed {
+                    writeln!(self.stream, "Failed to validate:")?;
+                    if args.deny_workspace_false {
+                        self.print_grouped_failures(&report.packages, args, show_configurations)?;
+                    } else {
+                        self.print_failures(
+                            "Failing packages:",
+                            &report.packages,
+                            args.suggest,
+                            show_configurations,
+                            args.dense,
+                        )?;
+                    }
+                } else if args.verbose {
+                    writeln!(self.stream, "All packages pass!")?;
+                }
+            }
+        }
+        match args.format() {
+            Format::Human | Format::Tree => self.print_warnings(report)?,
+            // These reports already include the warnings.
+            Format::Json | Format::Sarif => {}
+            // Keep the warnings out of the report on stdout.
+            Format::Github | Format::Junit | Format::Patch => {
+                Output::new(false, args.quiet, args.color, args.decorate).print_warnings(report)?;
+            }
+        }
+        Ok(!any_failed)
+    }
+
+    /// Print a line identifying this tool's version and what was checked, for context when the
+    /// output is saved. This is left out of undecorated output.
+    fn print_header(&mut self, report: &WorkspaceReport) -> io::Result<()> {
+        if !self.decorated {
+            return Ok(());
+        }
+        let members = report.packages.len();
+        let noun = if members == 1 { "member" } else { "members" };
+        let location = match &report.workspace_root {
+            Some(workspace_root) => format!("of {workspace_root}"),
+            None => "without a workspace root".to_owned(),
+        };
+        writeln!(
+            self.stream,
+            "{} {}: checking {members} {noun} {location}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        )
+    }
+
+    /// Print the warnings from advisory checks, if there are any.
+    fn print_warnings(&mut self, report: &WorkspaceReport) -> io::Result<()> {
+        let width = self.width;
+        let mut results = report
+            .packages
+            .iter()
+            .filter(|result| !result.result.warnings.is_empty())
+            .peekable();
+        if report.warnings.is_empty() && results.peek().is_none() {
+            return Ok(());
+        }
+        write!(self.stream, "Warnings:")?;
+        if !report.warnings.is_empty() {
+            writeln!(self.stream, "\n* Workspace:")?;
+            for warning in &report.warnings {
+                writeln!(
+                    self.stream,
+                    "{}",
+                    wrap(&warning.to_string(), "     ", width)
+                )?;
+            }
+        }
+        for result in results {
+            writeln!(self.stream, "\n* Package {}:", result.result.package)?;
+            for warning in &result.result.warnings {
+                writeln!(
+                    self.stream,
+                    "{}",
+                    wrap(&warning.to_string(), "     ", width)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the lints configured in the workspace root manifest.
+    fn print_root_lints(
+        &mut self,
+        workspace_root: &Utf8Path,
+        lints: Option<toml::Table>,
+    ) -> io::Result<()> {
+        let manifest_path = workspace_root.join("Cargo.toml");
+        let Some(lints) = lints else {
+            return writeln!(self.stream, "No `[workspace.lints]` in {manifest_path}");
+        };
+        let mut workspace = toml::Table::new();
+        workspace.insert("lints".to_owned(), lints.into());
+        let mut manifest = toml::Table::new();
+        manifest.insert("workspace".to_owned(), workspace.into());
+        writeln!(self.stream, "Workspace lints from {manifest_path}:")?;
+        write!(
+            self.stream,
+            "{}",
+            toml::to_string_pretty(&manifest).expect("TOML table serializes to TOML")
+        )
+    }
+
+    /// Print the failing packages in separate sections for those missing `lints.workspace`, those
+    /// explicitly opting out with `lints.workspace = false`, and any others, followed by a count of
+    /// each.
+    fn print_grouped_failures(
+        &mut self,
+        results: &[ConfiguredPackageResult],
+        args: &WorkspaceLintsArguments,
+        show_configurations: bool,
+    ) -> io::Result<()> {
+        let (mut missing, mut opted_out, mut other) = (Vec::new(), Vec::new(), Vec::new());
+        for result in results {
+            match &result.result.status {
+                PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsMissing) => {
+                    missing.push(result);
+                }
+                PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsWrongValue(
+                    toml::Value::Boolean(false),
+                )) => opted_out.push(result),
+                PackageStatus::Failed(_) => other.push(result),
+                PackageStatus::Passed | PackageStatus::Skipped(_) => {}
+            }
+        }
+        let sections = [
+            ("Missing:", &missing),
+            ("Explicitly opted out:", &opted_out),
+            ("Other failures:", &other),
+        ];
+        let mut first = true;
+        for (heading, section) in sections {
+            if section.is_empty() {
+                continue;
+            }
+            if !first && !args.dense {
+                writeln!(self.stream)?;
+            }
+            first = false;
+            self.print_failures(
+                heading,
+                section.iter().copied(),
+                args.suggest,
+                show_configurations,
+                args.dense,
+            )?;
+        }
+        write!(
+            self.stream,
+            "{} missing, {} explicitly disabled",
+            missing.len(),
+            opted_out.len()
+        )?;
+        if other.is_empty() {
+            writeln!(self.stream)
+        } else {
+            writeln!(self.stream, ", {} other", other.len())
+        }
+    }
+
+    /// Print the failing packages, wrapped to fit the terminal, under the given heading.
+    ///
+    /// If `suggest` is set, guidance on how to fix the failure is printed alongside the first
+    /// package failing with each kind. If `show_configurations` is set, the configurations which
+    /// surfaced each package are printed. If `dense` is set, packages aren't separated by blank
+    /// lines.
+    fn print_failures<'a>(
+        &mut self,
+        heading: &str,
+        results: impl IntoIterator<Item = &'a ConfiguredPackageResult>,
+        suggest: bool,
+        show_configurations: bool,
+        dense: bool,
+    ) -> io::Result<()> {
+        let width = self.width;
+        let mut seen_kinds = HashSet::new();
+        writeln!(self.stream, "{}", paint(heading, Color::Red, self.color))?;
+        let mut first = true;
+        for result in results {
+            let PackageStatus::Failed(kind) = &result.result.status else {
+                continue;
+            };
+            if !first && !dense {
+                writeln!(self.stream)?;
+            }
+            first = false;
+            writeln!(self.stream, "* Package {}:", result.result.package)?;
+            writeln!(self.stream, "{}", wrap(&kind.to_string(), "     ", width))?;
+            if show_configurations {
+                let configurations = result.configurations.join(", ");
+                writeln!(
+                    self.stream,
+                    "{}",
+                    wrap(&configurations, "     found with: ", width)
+                )?;
+            }
+            if suggest && seen_kinds.insert(mem::discriminant(kind)) {
+                let mut lines = kind.suggestion().lines();
+                if let Some(first_line) = lines.next() {
+                    writeln!(self.stream, "{}", wrap(first_line, "     help: ", width))?;
+                }
+                for line in lines {
+                    let content = line.trim_start();
+                    let indent = format!("     {}", &line[..line.len() - content.len()]);
+                    writeln!(self.stream, "{}", wrap(content, &indent, width))?;
+                }
+            }
+            if suggest {
+                self.print_suggested_change(&result.result)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask a question, returning the first letter of the answer read from stdin, in lowercase.
+    ///
+    /// The question is asked again until the answer is one of the letters in brackets in it.
+    /// Returns `'a'`, to abort, at the end of stdin.
+    fn prompt(&mut self, question: &str) -> io::Result<char> {
+        loop {
+            write!(self.stream, "{question}: ")?;
+            self.stream.flush()?;
+            let mut answer = String::new();
+            if io::stdin().read_line(&mut answer)? == 0 {
+                writeln!(self.stream)?;
+                return Ok('a');
+            }
+            if let Some(letter) = answer.trim().chars().next() {
+                let letter = letter.to_ascii_lowercase();
+                if question.contains(&format!("[{letter}]")) {
+                    return Ok(letter);
+                }
+            }
+        }
+    }
+
+    /// Print the change to the package's manifest which would fix it as a diff, if it is a
+    /// failure which `--fix` could fix.
+    fn print_suggested_change(&mut self, result: &PackageResult) -> io::Result<()> {
+        match cargo_workspace_lints::plan_fixes([result], FalseFix::SetTrue).pop() {
+            Some(fix) => self.print_fix_diff(&fix),
+            None => Ok(()),
+        }
+    }
+
+    /// Print the planned edit to a manifest as a diff, with a line of context either side.
+    ///
+    /// Nothing is printed if the manifest couldn't be read or edited.
+    fn print_fix_diff(&mut self, fix: &PlannedFix) -> io::Result<()> {
+        let (Ok(old), Ok(new)) = (
+            fs::read_to_string(&fix.manifest_path),
+            cargo_workspace_lints::fixed_manifest(fix),
+        ) else {
+            return Ok(());
+        };
+        let old = old.lines().collect::<Vec<_>>();
+        let new = new.lines().collect::<Vec<_>>();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        writeln!(
+            self.stream,
+            "     suggested change to {}:",
+            fix.manifest_path
+        )?;
+        if let Some(line) = prefix.checked_sub(1).map(|i| old[i]) {
+            writeln!(self.stream, "       {line}")?;
+        }
+        for line in &old[prefix..old.len() - suffix] {
+            let line = format!("     - {line}");
+            writeln!(
+                self.stream,
+                "{}",
+                paint(line.trim_end(), Color::Red, self.color)
+            )?;
+        }
+        for line in &new[prefix..new.len() - suffix] {
+            let line = format!("     + {line}");
+            writeln!(
+                self.stream,
+                "{}",
+                paint(line.trim_end(), Color::Green, self.color)
+            )?;
+        }
+        if let Some(line) = old.get(old.len() - suffix) {
+            writeln!(self.stream, "       {line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Wrap `text` to fit within `width` columns, starting each line with `indent`.
+///
+/// Lines are only broken at whitespace, so long tokens (such as paths) are never split, even if
+/// that means they overflow the line.
+fn wrap(text: &str, indent: &str, width: usize) -> String {
+    let continuation = " ".repeat(indent.len());
+    let mut output = indent.to_owned();
+    let mut line_len = indent.len();
+    let mut line_empty = true;
+    for word in text.split_whitespace() {
+        if !line_empty && line_len + 1 + word.len() > width {
+            output.push('\n');
+            output.push_str(&continuation);
+            line_len = continuation.len();
+            line_empty = true;
+        }
+        if !line_empty {
+            output.push(' ');
+            line_len += 1;
+        }
+        output.push_str(word);
+        line_len += word.len();
+        line_empty = false;
+    }
+    output
+}
+
+/// Write a manual page for the `cargo workspace-lints` command, in roff format.
+fn write_man_page(out: &mut impl Write) -> io::Result<()> {
+    let mut command = Arguments::command();
+    command.build();
+    let command = command
+        .find_subcommand("workspace-lints")
+        .expect("The subcommand is defined");
+    let name = env!("CARGO_PKG_NAME");
+    writeln!(
+        out,
+        ".TH {} 1 \"\" \"{name} {}\"",
+        name.to_uppercase(),
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(out, ".SH NAME")?;
+    writeln!(
+        out,
+        "{} \\- {}",
+        roff_escape(name),
+        roff_escape(
+            &command
+                .get_about()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        )
+    )?;
+    writeln!(out, ".SH SYNOPSIS")?;
+    // Everything but the prose comes from clap, so that the page can't drift from the arguments.
+    let usage = command.clone().render_usage().to_string();
+    let bin_name = command.get_bin_name().unwrap_or(name);
+    for (i, line) in usage
+        .trim_start_matches("Usage:")
+        .lines()
+        .map(str::trim)
+        .enumerate()
+    {
+        if i > 0 {
+            writeln!(out, ".br")?;
+        }
+        let rest = line.strip_prefix(bin_name).unwrap_or(line);
+        writeln!(
+            out,
+            "\\fB{}\\fR{}",
+            roff_escape(bin_name),
+            roff_escape(rest)
+        )?;
+    }
+    if let Some(about) = command.get_long_about().or_else(|| command.get_about()) {
+        writeln!(out, ".SH DESCRIPTION")?;
+        write_roff_paragraphs(out, &about.to_string())?;
+    }
+    writeln!(out, ".SH OPTIONS")?;
+    for arg in command.get_arguments().filter(|arg| !arg.is_hide_set()) {
+        write_roff_option(out, arg)?;
+    }
+    if let Some(after_help) = command.get_after_help() {
+        // The sections after the help are each introduced by a line like `Exit status:`.
+        for section in after_help.to_string().split("\n\n").map(str::trim) {
+            match section.split_once(":\n") {
+                Some((heading, body)) if !heading.contains('\n') => {
+                    writeln!(out, ".SH {}", roff_escape(&heading.to_uppercase()))?;
+                    writeln!(out, ".nf")?;
+                    for line in body.lines() {
+                        writeln!(out, "{}", roff_escape(line))?;
+                    }
+                    writeln!(out, ".fi")?;
+                }
+                _ => {
+                    writeln!(out, ".PP")?;
+                    write_roff_paragraphs(out, section)?;
+                }
+            }
+        }
+    }
+    writeln!(out, ".SH COMMANDS")?;
+    for subcommand in command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+    {
+        writeln!(out, ".TP")?;
+        writeln!(
+            out,
+            "\\fBcargo workspace\\-lints {}\\fR",
+            roff_escape(subcommand.get_name())
+        )?;
+        if let Some(about) = subcommand.get_about() {
+            write_roff_paragraphs(out, &about.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the entry for an argument in the options section of the manual page.
+fn write_roff_option(out: &mut impl Write, arg: &clap::Arg) -> io::Result<()> {
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("\\fB\\-{short}\\fR"));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("\\fB\\-\\-{}\\fR", roff_escape(long)));
+    }
+    let values = arg
+        .get_value_names()
+        .into_iter()
+        .flatten()
+        .map(|value| format!("\\fI{}\\fR", roff_escape(value)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    writeln!(out, ".TP")?;
+    match (names.is_empty(), values.is_empty()) {
+        (true, _) => writeln!(out, "[{values}]...")?,
+        (false, true) => writeln!(out, "{}", names.join(", "))?,
+        (false, false) => writeln!(out, "{} {values}", names.join(", "))?,
+    }
+    if let Some(help) = arg.get_long_help().or_else(|| arg.get_help()) {
+        write_roff_paragraphs(out, &help.to_string())?;
+    }
+    write_roff_possible_values(out, arg)?;
+    let defaults = arg
+        .get_default_values()
+        .iter()
+        .map(|value| roff_escape(&value.to_string_lossy()))
+        .collect::<Vec<_>>();
+    // Flags default to being unset, which goes without saying.
+    if !defaults.is_empty() && arg.get_action().takes_values() {
+        writeln!(out, ".sp")?;
+        writeln!(out, "[default: {}]", defaults.join(", "))?;
+    }
+    Ok(())
+}
+
+/// Write the values the argument can take, if it only takes some, each with its help.
+fn write_roff_possible_values(out: &mut impl Write, arg: &clap::Arg) -> io::Result<()> {
+    let values = arg
+        .get_possible_values()
+        .into_iter()
+        .filter(|value| !value.is_hide_set())
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, ".sp")?;
+    writeln!(out, "Possible values:")?;
+    writeln!(out, ".RS")?;
+    for value in values {
+        writeln!(out, ".TP")?;
+        writeln!(out, "\\fB{}\\fR", roff_escape(value.get_name()))?;
+        if let Some(help) = value.get_help() {
+            write_roff_paragraphs(out, &help.to_string())?;
+        }
+    }
+    writeln!(out, ".RE")
+}
+
+/// Write text as roff paragraphs, which are separated by blank lines.
+fn write_roff_paragraphs(out: &mut impl Write, text: &str) -> io::Result<()> {
+    for (i, paragraph) in text.split("\n\n").enumerate() {
+        if i > 0 {
+            writeln!(out, ".sp")?;
+        }
+        for line in paragraph.lines() {
+            writeln!(out, "{}", roff_escape(line.trim()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Escape text for roff, so that backslashes and hyphens print as themselves and lines starting
+/// with `.` or `'` aren't taken as requests.
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with(['.', '\'']) {
+        format!("\\&{escaped}")
+    } else {
+        escaped
     }
 }
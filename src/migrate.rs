@@ -0,0 +1,208 @@
+//! Moving the lints members define for themselves into the root `[workspace.lints]`.
+
+use cargo_metadata::{
+    camino::{Utf8Path, Utf8PathBuf},
+    Metadata,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+};
+
+use crate::{root, OptOut, WorkspaceValidationError};
+
+/// The edits which would move every member's own lints into the root `[workspace.lints]`.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    /// The root manifest, which `[workspace.lints]` is written to.
+    pub root_manifest_path: Utf8PathBuf,
+    /// The lints to add to the root `[workspace.lints]`, keyed by tool, which aren't there
+    /// already.
+    pub lints: toml::Table,
+    /// The manifests of the members to set `lints.workspace = true` in, replacing their own lint
+    /// tables.
+    pub members: Vec<Utf8PathBuf>,
+    /// Lints which members (or the root) set differently, so can't be merged.
+    pub conflicts: Vec<LintConflict>,
+}
+
+/// A lint which members set to different values.
+#[derive(Debug, Clone)]
+pub struct LintConflict {
+    /// The lint, with the tool's prefix for tools other than `rustc` (such as
+    /// `clippy::unwrap_used`).
+    pub lint: String,
+    /// Each value the lint is set to, with the name of the package (or `[workspace.lints]`) which
+    /// sets it.
+    pub values: Vec<(String, toml::Value)>,
+}
+
+/// Plan moving the lints workspace members define in their own `[lints]` tables into the root
+/// `[workspace.lints]`.
+///
+/// Every member which doesn't already set `lints.workspace = true` is included, except those
+/// which opt out by setting `package.metadata.workspace-lints.skip = true` with a `reason`.
+/// Lints already in the root `[workspace.lints]` are kept, and count as conflicts if a member sets
+/// them differently.
+///
+/// # Errors
+/// Returns an error if a manifest couldn't be read or parsed.
+pub fn plan_migration(metadata: &Metadata) -> Result<Migration, WorkspaceValidationError> {
+    let root_lints = root::read_workspace_lints(&metadata.workspace_root)?.unwrap_or_default();
+    // Each value every lint is set to, with the packages which set it to that value.
+    let mut values = BTreeMap::<(String, String), Vec<(toml::Value, Vec<String>)>>::new();
+    let mut members = Vec::new();
+    let workspace_members = metadata.workspace_members.iter().collect::<HashSet<_>>();
+    for package in metadata
+        .packages
+        .iter()
+        .filter(|package| workspace_members.contains(&package.id))
+    {
+        if let OptOut::Justified(_) = OptOut::from_metadata(&package.metadata) {
+            continue;
+        }
+        let path = &package.manifest_path;
+        let manifest: toml::Table =
+            toml::from_str(&read(path)?).map_err(|error| WorkspaceValidationError::Toml {
+                path: path.clone(),
+                error,
+            })?;
+        let lints = manifest.get("lints").and_then(toml::Value::as_table);
+        if lints.and_then(|lints| lints.get("workspace")) == Some(&toml::Value::Boolean(true)) {
+            continue;
+        }
+        members.push(path.clone());
+        let tools = lints
+            .into_iter()
+            .flatten()
+            .filter_map(|(tool, lints)| Some((tool, lints.as_table()?)));
+        for (tool, lints) in tools {
+            for (lint, value) in lints {
+                let settings = values.entry((tool.clone(), lint.clone())).or_default();
+                match settings.iter_mut().find(|(known, _)| known == value) {
+                    Some((_, packages)) => packages.push(package.name.clone()),
+                    None => settings.push((value.clone(), vec![package.name.clone()])),
+                }
+            }
+        }
+    }
+    let mut lints = toml::Table::new();
+    let mut conflicts = Vec::new();
+    for ((tool, lint), settings) in values {
+        let root_value = root_lints.get(&tool).and_then(|lints| lints.get(&lint));
+        match (&settings[..], root_value) {
+            ([(value, _)], None) => {
+                if let toml::Value::Table(tool_lints) = lints
+                    .entry(tool)
+                    .or_insert_with(|| toml::Table::new().into())
+                {
+                    tool_lints.insert(lint, value.clone());
+                }
+            }
+            ([(value, _)], Some(root_value)) if root_value == value => {}
+            _ => conflicts.push(LintConflict {
+                lint: if tool == "rust" {
+                    lint
+                } else {
+                    format!("{tool}::{lint}")
+                },
+                values: root_value
+                    .map(|value| ("[workspace.lints]".to_owned(), value.clone()))
+                    .into_iter()
+                    .chain(settings.into_iter().flat_map(|(value, packages)| {
+                        packages
+                            .into_iter()
+                            .map(move |package| (package, value.clone()))
+                    }))
+                    .collect(),
+            }),
+        }
+    }
+    Ok(Migration {
+        root_manifest_path: metadata.workspace_root.join("Cargo.toml"),
+        lints,
+        members,
+        conflicts,
+    })
+}
+
+/// Make the edits in the migration: add the lints to the root `[workspace.lints]`, then replace
+/// the lint tables of each member with `lints.workspace = true`.
+///
+/// The manifests are edited with `toml_edit`, so comments and formatting elsewhere in them are
+/// preserved. This shouldn't be applied while the migration has conflicts, since the members'
+/// conflicting lints would be lost.
+///
+/// # Errors
+/// Returns an error, naming the manifest, if a manifest couldn't be read, parsed, or written.
+pub fn apply_migration(migration: &Migration) -> Result<(), WorkspaceValidationError> {
+    edit(&migration.root_manifest_path, |manifest| {
+        let workspace = manifest
+            .entry("workspace")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| invalid("`workspace` is not a table"))?;
+        let workspace_lints = workspace
+            .entry("lints")
+            .or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+            .as_table_like_mut()
+            .ok_or_else(|| invalid("`workspace.lints` is not a table"))?;
+        for (tool, lints) in &migration.lints {
+            let tool_lints = workspace_lints
+                .entry(tool)
+                .or_insert_with(toml_edit::table)
+                .as_table_like_mut()
+                .ok_or_else(|| invalid("tool tables in `workspace.lints` must be tables"))?;
+            for (lint, value) in lints.as_table().into_iter().flatten() {
+                let value = value
+                    .to_string()
+                    .parse::<toml_edit::Value>()
+                    .map_err(|e| invalid(e.to_string()))?;
+                tool_lints.insert(lint, toml_edit::value(value));
+            }
+        }
+        Ok(())
+    })?;
+    for path in &migration.members {
+        edit(path, |manifest| {
+            let mut lints = toml_edit::Table::new();
+            lints.insert("workspace", toml_edit::value(true));
+            manifest.insert("lints", toml_edit::Item::Table(lints));
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// Edit the manifest at `path` in place with `f`.
+fn edit(
+    path: &Utf8Path,
+    f: impl FnOnce(&mut toml_edit::Document) -> io::Result<()>,
+) -> Result<(), WorkspaceValidationError> {
+    let io_error = |error| WorkspaceValidationError::Io {
+        path: path.to_owned(),
+        error,
+    };
+    let mut manifest = read(path)?
+        .parse::<toml_edit::Document>()
+        .map_err(|e| io_error(invalid(e.to_string())))?;
+    f(&mut manifest).map_err(io_error)?;
+    fs::write(path, manifest.to_string()).map_err(io_error)
+}
+
+/// Read the manifest at `path`.
+fn read(path: &Utf8Path) -> Result<String, WorkspaceValidationError> {
+    fs::read_to_string(path).map_err(|error| WorkspaceValidationError::Io {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+/// An error for a manifest which doesn't have the expected structure.
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,56 @@
+//! Helpers for displaying and matching paths within a workspace.
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use std::{collections::BTreeSet, path::Path};
+
+/// Express `path` relative to `base`, using `..` components where `path` isn't under `base`.
+///
+/// This is purely lexical, so both paths should already be normalized (as the paths from `cargo
+/// metadata` are). If `path` and `base` are the same, this returns an empty path.
+pub(crate) fn relative_path(path: &Utf8Path, base: &Utf8Path) -> Utf8PathBuf {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    while let (Some(a), Some(b)) = (path_components.peek(), base_components.peek()) {
+        if a != b {
+            break;
+        }
+        path_components.next();
+        base_components.next();
+    }
+    base_components
+        .map(|_| Utf8Path::new(".."))
+        .chain(path_components.map(|component| Utf8Path::new(component.as_str())))
+        .collect()
+}
+
+/// Check whether `path` is inside the directory `dir`.
+///
+/// Both paths are canonicalized first, so that symlinks are resolved. A path which can't be
+/// canonicalized (such as one which doesn't exist) is compared as it is.
+pub(crate) fn is_within(path: &Utf8Path, dir: &Utf8Path) -> bool {
+    let canonical = |path: &Utf8Path| {
+        path.as_std_path()
+            .canonicalize()
+            .unwrap_or_else(|_| path.as_std_path().to_owned())
+    };
+    canonical(path).starts_with::<&Path>(&canonical(dir))
+}
+
+/// Add every `.rs` file under the directory `dir` to `paths`, recursively.
+///
+/// Entries which can't be read, or whose paths aren't UTF-8, are skipped.
+pub(crate) fn rust_sources(dir: &Utf8Path, paths: &mut BTreeSet<Utf8PathBuf>) {
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => rust_sources(path, paths),
+            Ok(file_type) if file_type.is_file() && path.extension() == Some("rs") => {
+                paths.insert(path.to_owned());
+            }
+            _ => {}
+        }
+    }
+}
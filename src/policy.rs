@@ -0,0 +1,539 @@
+//! Policies for the lints defined in the root `[workspace.lints]`.
+
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt, io,
+    process::Command,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::{filter::glob_matches, WorkspaceValidationError};
+
+/// The level a lint is set to, ordered from least to most strict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is turned off.
+    Allow,
+    /// The lint produces a warning.
+    Warn,
+    /// The lint produces an error.
+    Deny,
+    /// The lint produces an error, and can't be allowed again further in.
+    Forbid,
+}
+
+impl LintLevel {
+    /// Parse a lint level as written in a `[lints]` table, such as `deny`.
+    ///
+    /// `expect` is treated as `allow`, since it doesn't report the lint either.
+    #[must_use]
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "allow" | "expect" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            "forbid" => Some(Self::Forbid),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+            Self::Forbid => "forbid",
+        })
+    }
+}
+
+/// A lint which must be set in the root `[workspace.lints]` to at least the given level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredLint {
+    /// The lint, with the tool's prefix as in source code for tools other than `rustc` (such as
+    /// `clippy::unwrap_used`).
+    pub lint: String,
+    /// The least strict level the lint may be set to.
+    pub level: LintLevel,
+}
+
+impl RequiredLint {
+    /// Parse a required lint of the form `LINT=LEVEL`, such as `unsafe_code=deny`.
+    ///
+    /// Returns `None` if there is no `=` or the level isn't known.
+    #[must_use]
+    pub fn parse(requirement: &str) -> Option<Self> {
+        let (lint, level) = requirement.split_once('=')?;
+        Some(Self {
+            lint: lint.trim().to_owned(),
+            level: LintLevel::parse(level.trim())?,
+        })
+    }
+}
+
+/// Split a lint as written in source code into its tool and name, such as `("clippy", "all")`
+/// for `clippy::all`.
+pub(crate) fn split_lint(lint: &str) -> (&str, &str) {
+    lint.split_once("::").unwrap_or(("rust", lint))
+}
+
+/// The level a lint is set to in a `[lints]` tool table, whether given directly or as a table
+/// with a `level` key.
+pub(crate) fn lint_level(config: &toml::Value) -> Option<LintLevel> {
+    LintLevel::parse(config.as_str().or_else(|| config.get("level")?.as_str())?)
+}
+
+/// Find the required lints which the root `[workspace.lints]` doesn't set, or sets to a less
+/// strict level than required.
+///
+/// Only lints listed by name are considered, so a lint which is only set through a group it
+/// belongs to (such as `clippy::pedantic`) counts as missing.
+pub(crate) fn weak_required_lints(
+    workspace_lints: &toml::Table,
+    required_lints: &[RequiredLint],
+) -> Vec<String> {
+    required_lints
+        .iter()
+        .filter_map(|required| {
+            let (tool, name) = split_lint(&required.lint);
+            let level = workspace_lints
+                .get(tool)
+                .and_then(|lints| lints.get(name))
+                .map(lint_level);
+            match level {
+                None => Some(format!(
+                    "`{}` isn't set, but must be at least `{}`",
+                    required.lint, required.level
+                )),
+                Some(None) => Some(format!(
+                    "`{}` has an unknown level, but must be at least `{}`",
+                    required.lint, required.level
+                )),
+                Some(Some(level)) if level < required.level => Some(format!(
+                    "`{}` is `{level}`, but must be at least `{}`",
+                    required.lint, required.level
+                )),
+                Some(Some(_)) => None,
+            }
+        })
+        .collect()
+}
+
+/// Find the lints which the root `[workspace.lints]` sets to `allow`, other than those matching
+/// one of the `permitted` globs (such as `clippy::module_name_repetitions` or `clippy::*`).
+pub(crate) fn allowed_root_lints(
+    workspace_lints: &toml::Table,
+    permitted: &[String],
+) -> Vec<String> {
+    workspace_lints
+        .iter()
+        .filter_map(|(tool, lints)| Some((tool, lints.as_table()?)))
+        .flat_map(|(tool, lints)| {
+            lints
+                .iter()
+                .filter(|(_, config)| lint_level(config) == Some(LintLevel::Allow))
+                .map(move |(name, _)| {
+                    if tool == "rust" {
+                        name.clone()
+                    } else {
+                        format!("{tool}::{name}")
+                    }
+                })
+        })
+        .filter(|lint| {
+            !permitted
+                .iter()
+                .any(|glob| glob_matches(glob.as_bytes(), lint.as_bytes()))
+        })
+        .map(|lint| format!("`{lint}` is set to `allow`"))
+        .collect()
+}
+
+/// The program which lists the lints for each lint tool, with `-W help`.
+pub(crate) const LINT_TOOLS: &[(&str, &str)] = &[
+    ("rust", "rustc"),
+    ("clippy", "clippy-driver"),
+    ("rustdoc", "rustdoc"),
+];
+
+/// Find the lints in the root `[workspace.lints]` which aren't known lints or lint groups of
+/// their tool.
+///
+/// The known lints are listed by running `rustc`, `clippy-driver`, or `rustdoc` with `-W help`,
+/// so they are those of the toolchain on the `PATH`. Each program is only run if the workspace
+/// sets lints for its tool.
+///
+/// # Errors
+/// Returns an error, naming the program, if one of the programs couldn't be run.
+pub(crate) fn unknown_root_lints(
+    workspace_lints: &toml::Table,
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    let mut problems = Vec::new();
+    for (tool, lints) in workspace_lints {
+        let Some(lints) = lints.as_table() else {
+            continue;
+        };
+        // Cargo itself rejects tables for any other tools.
+        let Some((_, program)) = LINT_TOOLS.iter().find(|(known, _)| known == tool) else {
+            continue;
+        };
+        let known = known_lints(program)?;
+        problems.extend(lints.keys().filter_map(|lint| {
+            let lint = if tool == "rust" {
+                lint.clone()
+            } else {
+                format!("{tool}::{lint}")
+            };
+            (!known.contains(&lint.replace('-', "_")))
+                .then(|| format!("`{lint}` isn't a known lint or lint group"))
+        }));
+    }
+    Ok(problems)
+}
+
+/// Find the lints in the root `[workspace.lints]` which a lint group in the same tool table may
+/// override, because the group sets a different level and its `priority` isn't lower.
+///
+/// Cargo passes lints to the compiler ordered by `priority`, and later ones take precedence, so a
+/// group at the same priority as one of its lints may override it depending only on their names.
+/// The members of each group are listed by running `rustc`, `clippy-driver`, or `rustdoc` with
+/// `-W help`, as for [`unknown_root_lints`].
+///
+/// # Errors
+/// Returns an error, naming the program, if one of the programs couldn't be run.
+pub(crate) fn shadowed_root_lints(
+    workspace_lints: &toml::Table,
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    let mut problems = Vec::new();
+    for (tool, lints) in workspace_lints {
+        let Some(lints) = lints.as_table() else {
+            continue;
+        };
+        let Some((_, program)) = LINT_TOOLS.iter().find(|(known, _)| known == tool) else {
+            continue;
+        };
+        let qualify = |name: &str| {
+            if tool == "rust" {
+                name.to_owned()
+            } else {
+                format!("{tool}::{name}")
+            }
+        };
+        let groups = lint_groups(program)?;
+        for (group, group_config) in lints {
+            let Some(members) = groups.get(&qualify(group).replace('-', "_")) else {
+                continue;
+            };
+            let Some(group_level) = lint_level(group_config) else {
+                continue;
+            };
+            for (lint, config) in lints {
+                let name = qualify(lint).replace('-', "_");
+                if groups.contains_key(&name) || !members.contains(&name) {
+                    continue;
+                }
+                let Some(level) = lint_level(config) else {
+                    continue;
+                };
+                if level != group_level && priority(config) <= priority(group_config) {
+                    problems.push(format!(
+                        "`{}` is set to `{level}`, but may be overridden by the group `{}`, which \
+                         sets it to `{group_level}`; give the group a lower `priority`",
+                        qualify(lint),
+                        qualify(group),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// Lints which have been renamed, with their new names, qualified with the tool's prefix as in
+/// source code.
+///
+/// This includes lints which have moved between tools, such as from `clippy` into `rustc`, or from
+/// `rustc` into `rustdoc`.
+const RENAMED_LINTS: &[(&str, &str)] = &[
+    ("async_idents", "keyword_idents"),
+    ("bare_trait_object", "bare_trait_objects"),
+    ("broken_intra_doc_links", "rustdoc::broken_intra_doc_links"),
+    (
+        "disjoint_capture_migration",
+        "rust_2021_incompatible_closure_captures",
+    ),
+    ("exceeding_bitshifts", "arithmetic_overflow"),
+    (
+        "invalid_codeblock_attributes",
+        "rustdoc::invalid_codeblock_attributes",
+    ),
+    ("invalid_html_tags", "rustdoc::invalid_html_tags"),
+    (
+        "missing_crate_level_docs",
+        "rustdoc::missing_crate_level_docs",
+    ),
+    (
+        "missing_doc_code_examples",
+        "rustdoc::missing_doc_code_examples",
+    ),
+    ("non_autolinks", "rustdoc::bare_urls"),
+    ("non_fmt_panic", "non_fmt_panics"),
+    (
+        "or_patterns_back_compat",
+        "rust_2021_incompatible_or_patterns",
+    ),
+    ("overlapping_patterns", "overlapping_range_endpoints"),
+    ("private_doc_tests", "rustdoc::private_doc_tests"),
+    (
+        "private_intra_doc_links",
+        "rustdoc::private_intra_doc_links",
+    ),
+    ("redundant_semicolon", "redundant_semicolons"),
+    ("unstable_name_collision", "unstable_name_collisions"),
+    (
+        "clippy::almost_complete_letter_range",
+        "clippy::almost_complete_range",
+    ),
+    ("clippy::blacklisted_name", "clippy::disallowed_names"),
+    ("clippy::box_vec", "clippy::box_collection"),
+    (
+        "clippy::const_static_lifetime",
+        "clippy::redundant_static_lifetimes",
+    ),
+    (
+        "clippy::cyclomatic_complexity",
+        "clippy::cognitive_complexity",
+    ),
+    (
+        "clippy::derive_hash_xor_eq",
+        "clippy::derived_hash_with_manual_eq",
+    ),
+    ("clippy::disallowed_method", "clippy::disallowed_methods"),
+    ("clippy::disallowed_type", "clippy::disallowed_types"),
+    ("clippy::drop_bounds", "drop_bounds"),
+    (
+        "clippy::eval_order_dependence",
+        "clippy::mixed_read_write_in_expression",
+    ),
+    ("clippy::identity_conversion", "clippy::useless_conversion"),
+    ("clippy::if_let_some_result", "clippy::match_result_ok"),
+    ("clippy::into_iter_on_array", "array_into_iter"),
+    ("clippy::invalid_atomic_ordering", "invalid_atomic_ordering"),
+    ("clippy::invalid_ref", "invalid_value"),
+    ("clippy::let_underscore_drop", "let_underscore_drop"),
+    ("clippy::logic_bug", "clippy::overly_complex_bool_expr"),
+    (
+        "clippy::mem_discriminant_non_enum",
+        "enum_intrinsics_non_enums",
+    ),
+    (
+        "clippy::new_without_default_derive",
+        "clippy::new_without_default",
+    ),
+    (
+        "clippy::option_and_then_some",
+        "clippy::bind_instead_of_map",
+    ),
+    ("clippy::option_expect_used", "clippy::expect_used"),
+    ("clippy::option_map_unwrap_or", "clippy::map_unwrap_or"),
+    ("clippy::option_map_unwrap_or_else", "clippy::map_unwrap_or"),
+    ("clippy::option_unwrap_used", "clippy::unwrap_used"),
+    ("clippy::panic_params", "non_fmt_panics"),
+    (
+        "clippy::positional_named_format_parameters",
+        "named_arguments_used_positionally",
+    ),
+    ("clippy::result_expect_used", "clippy::expect_used"),
+    ("clippy::result_map_unwrap_or_else", "clippy::map_unwrap_or"),
+    ("clippy::result_unwrap_used", "clippy::unwrap_used"),
+    (
+        "clippy::single_char_push_str",
+        "clippy::single_char_add_str",
+    ),
+    ("clippy::stutter", "clippy::module_name_repetitions"),
+    (
+        "clippy::to_string_in_display",
+        "clippy::recursive_format_impl",
+    ),
+    (
+        "clippy::undropped_manually_drops",
+        "undropped_manually_drops",
+    ),
+    ("clippy::unknown_clippy_lints", "unknown_lints"),
+    ("clippy::unused_label", "unused_labels"),
+    ("clippy::zero_width_space", "clippy::invisible_characters"),
+    ("rustdoc::non_autolinks", "rustdoc::bare_urls"),
+];
+
+/// Lints which have been removed, and no longer have any effect.
+const REMOVED_LINTS: &[&str] = &[
+    "clippy::assign_ops",
+    "clippy::extend_from_slice",
+    "clippy::filter_map",
+    "clippy::find_map",
+    "clippy::if_let_redundant_pattern_matching",
+    "clippy::misaligned_transmute",
+    "clippy::pub_enum_variant_names",
+    "clippy::range_step_by_zero",
+    "clippy::regex_macro",
+    "clippy::replace_consts",
+    "clippy::should_assert_eq",
+    "clippy::unsafe_vector_initialization",
+    "clippy::unstable_as_mut_slice",
+    "clippy::unstable_as_slice",
+    "clippy::unused_collect",
+    "clippy::wrong_pub_self_convention",
+];
+
+/// Find the lints in the root `[workspace.lints]` which have been renamed or removed, or which are
+/// set more than once across its tool tables.
+///
+/// A lint is set more than once if it's listed under several names which mean the same lint,
+/// such as both its old and new names (which may be in different tool tables), or both with `-`
+/// and with `_`. Unlike [`unknown_root_lints`], this doesn't need to run any programs, so only
+/// knows about the lints listed here.
+pub(crate) fn inconsistent_root_lints(workspace_lints: &toml::Table) -> Vec<String> {
+    let mut problems = Vec::new();
+    // The names each lint is set under, with the levels they set, keyed by its current name.
+    let mut names = BTreeMap::<String, Vec<(String, Option<LintLevel>)>>::new();
+    for (tool, lints) in workspace_lints {
+        let Some(lints) = lints.as_table() else {
+            continue;
+        };
+        for (lint, config) in lints {
+            let lint = if tool == "rust" {
+                lint.clone()
+            } else {
+                format!("{tool}::{lint}")
+            };
+            let normalized = lint.replace('-', "_");
+            let current = match RENAMED_LINTS.iter().find(|(old, _)| *old == normalized) {
+                Some((_, new)) => {
+                    problems.push(format!("`{lint}` has been renamed to `{new}`"));
+                    (*new).to_owned()
+                }
+                None if REMOVED_LINTS.contains(&normalized.as_str()) => {
+                    problems.push(format!("`{lint}` has been removed, so has no effect"));
+                    continue;
+                }
+                None => normalized,
+            };
+            names
+                .entry(current)
+                .or_default()
+                .push((lint, lint_level(config)));
+        }
+    }
+    for (lint, settings) in names {
+        if settings.len() < 2 {
+            continue;
+        }
+        let listed = settings
+            .iter()
+            .map(|(name, level)| match level {
+                Some(level) => format!("`{name}` (`{level}`)"),
+                None => format!("`{name}`"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let conflicting = settings.iter().any(|(_, level)| *level != settings[0].1);
+        problems.push(if conflicting {
+            format!("`{lint}` is set to conflicting levels, as {listed}")
+        } else {
+            format!("`{lint}` is set more than once, as {listed}")
+        });
+    }
+    problems
+}
+
+/// The `priority` a lint is given in a `[lints]` tool table, which defaults to 0.
+pub(crate) fn priority(config: &toml::Value) -> i64 {
+    config
+        .get("priority")
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(0)
+}
+
+/// List the lints and lint groups which `program` knows about, with `-` replaced by `_`.
+fn known_lints(program: &str) -> Result<HashSet<String>, WorkspaceValidationError> {
+    // Each lint or group is listed at the start of a line of its table, after the column headers.
+    Ok(lint_help(program)?
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| {
+            *name != "name"
+                && name.starts_with(|c: char| c.is_ascii_lowercase())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_:".contains(c))
+        })
+        .map(|name| name.replace('-', "_"))
+        .collect())
+}
+
+/// List the lint groups which `program` knows about, with the lints in each, with `-` replaced
+/// by `_`.
+pub(crate) fn lint_groups(
+    program: &str,
+) -> Result<HashMap<String, HashSet<String>>, WorkspaceValidationError> {
+    let mut groups = HashMap::new();
+    let mut in_groups = false;
+    for line in lint_help(program)?.lines() {
+        // Each table is introduced by an unindented heading.
+        if !line.starts_with(char::is_whitespace) && !line.is_empty() {
+            in_groups = line.starts_with("Lint groups provided by");
+            continue;
+        }
+        let Some((group, members)) = line.trim().split_once(char::is_whitespace) else {
+            continue;
+        };
+        if in_groups && group != "name" && group != "----" {
+            let members = members
+                .split(',')
+                .map(|lint| lint.trim().replace('-', "_"))
+                .collect();
+            groups.insert(group.replace('-', "_"), members);
+        }
+    }
+    Ok(groups)
+}
+
+/// The output of `-W help` for each program which has been run, so that each is only run once.
+static LINT_HELP: Mutex<BTreeMap<String, Arc<str>>> = Mutex::new(BTreeMap::new());
+
+/// The output of running `program` with `-W help`, which lists its lints and lint groups.
+///
+/// The output is kept for the rest of the process once `program` has run successfully.
+fn lint_help(program: &str) -> Result<Arc<str>, WorkspaceValidationError> {
+    if let Some(help) = LINT_HELP
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(program)
+    {
+        return Ok(Arc::clone(help));
+    }
+    let unrunnable = |error| WorkspaceValidationError::Io {
+        path: program.into(),
+        error,
+    };
+    let output = Command::new(program)
+        .args(["-W", "help"])
+        .output()
+        .map_err(unrunnable)?;
+    if !output.status.success() {
+        return Err(unrunnable(io::Error::new(
+            io::ErrorKind::Other,
+            format!("`{program} -W help` failed ({})", output.status),
+        )));
+    }
+    let help = Arc::<str>::from(String::from_utf8_lossy(&output.stdout));
+    LINT_HELP
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(program.to_owned(), Arc::clone(&help));
+    Ok(help)
+}
@@ -0,0 +1,68 @@
+//! Reporting the progress of a check, such as the `PASS:` and `FAIL:` line for each package.
+
+use std::{fmt, io, sync::Mutex};
+
+/// Where the progress of a check is reported, when it's verbose.
+///
+/// Set [`ValidationOptions::reporter`](crate::ValidationOptions::reporter) to capture or
+/// re-format the progress lines, rather than printing them to stderr. Any `Mutex` around an
+/// [`io::Write`] (such as a `Vec<u8>`) can be used to collect them as text.
+///
+/// Packages may be checked in parallel, but the lines about each package are held back until the
+/// packages before it have been reported, and then delivered together from the thread which
+/// started the check. So the lines arrive in the order the packages are listed, without lines
+/// about different packages interleaving.
+pub trait Reporter: fmt::Debug + Send + Sync {
+    /// Report a single line of progress, without a trailing newline.
+    fn report(&self, line: &str);
+
+    /// Note that a check has reached the given stage, such as to show a progress bar.
+    ///
+    /// Unlike [`Reporter::report`], this is called whether or not the check is verbose. It does
+    /// nothing by default.
+    ///
+    /// This crate has no `tracing` instrumentation of its own, so this is also the place to time
+    /// each stage of a run, such as by entering a span in the caller's tracing setup.
+    fn stage(&self, stage: Stage) {
+        let _ = stage;
+    }
+}
+
+/// How far a check has got, as given to [`Reporter::stage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Stage {
+    /// `cargo metadata` is being run for the workspace.
+    Metadata,
+    /// The packages are being checked, of which `checked` out of `total` are done.
+    ///
+    /// This is first given with `checked` at zero. Unlike the lines given to
+    /// [`Reporter::report`], this comes from whichever thread checked the package, as soon as it
+    /// finishes, so the counts can arrive out of order.
+    Packages {
+        /// The number of packages which have been checked.
+        checked: usize,
+        /// The number of packages to check.
+        total: usize,
+    },
+}
+
+/// Reports progress to stderr, which is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrReporter;
+
+impl Reporter for StderrReporter {
+    fn report(&self, line: &str) {
+        eprintln!("{line}");
+    }
+}
+
+impl<W: io::Write + Send + fmt::Debug> Reporter for Mutex<W> {
+    fn report(&self, line: &str) {
+        let mut writer = self
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // Progress is best-effort, so failing to write it doesn't fail the check.
+        let _ = writeln!(writer, "{line}");
+    }
+}
@@ -0,0 +1,196 @@
+//! Reading the workspace root manifest.
+
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use std::{collections::BTreeSet, fs};
+
+use crate::{path::relative_path, WorkspaceValidationError};
+
+/// Read the `[workspace.lints]` table from the root manifest of the workspace.
+///
+/// Returns `None` if the root manifest has no `[workspace.lints]` table.
+///
+/// # Errors
+/// Returns an error if the root manifest couldn't be read and parsed.
+pub fn read_workspace_lints(
+    workspace_root: &Utf8Path,
+) -> Result<Option<toml::Table>, WorkspaceValidationError> {
+    let path = workspace_root.join("Cargo.toml");
+    let manifest: toml::Table = toml::from_str(&read_manifest(&path)?)
+        .map_err(|error| WorkspaceValidationError::Toml { path, error })?;
+    Ok(manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("lints"))
+        .and_then(toml::Value::as_table)
+        .cloned())
+}
+
+/// Whether the root manifest has a `[workspace]` table, rather than being a single package which
+/// `cargo metadata` treats as its own workspace.
+///
+/// # Errors
+/// Returns an error if the root manifest couldn't be read and parsed.
+pub(crate) fn is_workspace(workspace_root: &Utf8Path) -> Result<bool, WorkspaceValidationError> {
+    let path = workspace_root.join("Cargo.toml");
+    let manifest: toml::Table = toml::from_str(&read_manifest(&path)?)
+        .map_err(|error| WorkspaceValidationError::Toml { path, error })?;
+    Ok(manifest.contains_key("workspace"))
+}
+
+/// Read the names of the dependencies in `[workspace.dependencies]` of the root manifest.
+///
+/// # Errors
+/// Returns an error if the root manifest couldn't be read and parsed.
+pub(crate) fn read_workspace_dependencies(
+    workspace_root: &Utf8Path,
+) -> Result<BTreeSet<String>, WorkspaceValidationError> {
+    let path = workspace_root.join("Cargo.toml");
+    let manifest: toml::Table = toml::from_str(&read_manifest(&path)?)
+        .map_err(|error| WorkspaceValidationError::Toml { path, error })?;
+    Ok(manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(toml::Value::as_table)
+        .map(|dependencies| dependencies.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+/// Find the first lint in each tool table of the root `[workspace.lints]` which is out of
+/// alphabetical order.
+///
+/// The manifest is read preserving the order of its keys, so this reflects the order in which the
+/// lints are written. Returns a description of each out-of-order lint found.
+///
+/// # Errors
+/// Returns an error if the root manifest couldn't be read and parsed.
+pub(crate) fn unsorted_workspace_lints(
+    workspace_root: &Utf8Path,
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    let path = workspace_root.join("Cargo.toml");
+    let manifest = read_manifest(&path)?
+        .parse::<toml_edit::Document>()
+        .map_err(|error| WorkspaceValidationError::Toml {
+            path,
+            error: serde::de::Error::custom(error),
+        })?;
+    let Some(lints) = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("lints"))
+        .and_then(toml_edit::Item::as_table_like)
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(lints
+        .iter()
+        .filter_map(|(tool, lints)| Some((tool, lints.as_table_like()?)))
+        .filter_map(|(tool, lints)| {
+            let keys = lints.iter().map(|(lint, _)| lint).collect::<Vec<_>>();
+            let (previous, lint) = keys
+                .windows(2)
+                .find(|pair| pair[0] > pair[1])
+                .map(|pair| (pair[0], pair[1]))?;
+            Some(format!(
+                "`workspace.lints.{tool}.{lint}` is out of order; it should come before \
+                 `{previous}`"
+            ))
+        })
+        .collect())
+}
+
+/// Find the root manifests of every workspace under `dir`, including `dir` itself.
+///
+/// Directories named `target` or starting with `.` are not searched, and manifests which can't be
+/// parsed are passed over rather than treated as errors. The manifest paths are returned sorted.
+///
+/// # Errors
+/// Returns an error if a directory couldn't be read.
+pub fn find_workspace_roots(dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>, WorkspaceValidationError> {
+    Ok(find_manifests(dir)?
+        .into_iter()
+        .filter(|(_, manifest)| manifest.contains_key("workspace"))
+        .map(|(path, _)| path)
+        .collect())
+}
+
+/// Find the packages and nested workspaces under the workspace root which escape its checks,
+/// returning a description of each.
+///
+/// These are the roots of other workspaces, and packages which aren't among `member_manifests`
+/// (such as those listed in `workspace.exclude`), other than those in the nested workspaces.
+/// Manifests are found as for [`find_workspace_roots`].
+///
+/// # Errors
+/// Returns an error if a directory couldn't be read.
+pub(crate) fn escaped_packages(
+    workspace_root: &Utf8Path,
+    member_manifests: &[&Utf8Path],
+) -> Result<Vec<String>, WorkspaceValidationError> {
+    let root_manifest_path = workspace_root.join("Cargo.toml");
+    let manifests = find_manifests(workspace_root)?;
+    let nested_roots = manifests
+        .iter()
+        .filter(|(path, manifest)| {
+            *path != root_manifest_path && manifest.contains_key("workspace")
+        })
+        .filter_map(|(path, _)| path.parent())
+        .collect::<Vec<_>>();
+    let mut problems = Vec::new();
+    for (path, manifest) in &manifests {
+        let relative = relative_path(path, workspace_root);
+        if nested_roots.contains(&path.parent().unwrap_or(path)) {
+            problems.push(format!(
+                "`{relative}` is the root of a nested workspace, which isn't checked with this one"
+            ));
+        } else if manifest.contains_key("package")
+            && !member_manifests.contains(&path.as_path())
+            && !nested_roots.iter().any(|root| path.starts_with(root))
+        {
+            problems.push(format!(
+                "`{relative}` is a package in the workspace's directory which isn't a member, so \
+                 isn't checked"
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+/// Find and parse every manifest under `dir`, including in `dir` itself, as for
+/// [`find_workspace_roots`].
+fn find_manifests(
+    dir: &Utf8Path,
+) -> Result<Vec<(Utf8PathBuf, toml::Table)>, WorkspaceValidationError> {
+    let mut manifests = Vec::new();
+    let mut pending = vec![dir.to_owned()];
+    while let Some(dir) = pending.pop() {
+        let unreadable = |error| WorkspaceValidationError::Io {
+            path: dir.clone(),
+            error,
+        };
+        for entry in dir.read_dir_utf8().map_err(unreadable)? {
+            let entry = entry.map_err(unreadable)?;
+            let path = entry.path();
+            if entry.file_type().map_err(unreadable)?.is_dir() {
+                let name = entry.file_name();
+                if name != "target" && !name.starts_with('.') {
+                    pending.push(path.to_owned());
+                }
+            } else if entry.file_name() == "Cargo.toml" {
+                let manifest = fs::read_to_string(path)
+                    .ok()
+                    .and_then(|contents| toml::from_str::<toml::Table>(&contents).ok());
+                if let Some(manifest) = manifest {
+                    manifests.push((path.to_owned(), manifest));
+                }
+            }
+        }
+    }
+    manifests.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(manifests)
+}
+
+/// Read the manifest at `path`.
+pub(crate) fn read_manifest(path: &Utf8Path) -> Result<String, WorkspaceValidationError> {
+    fs::read_to_string(path).map_err(|error| WorkspaceValidationError::Io {
+        path: path.to_owned(),
+        error,
+    })
+}
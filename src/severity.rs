@@ -0,0 +1,129 @@
+//! Configuring how seriously the problems found by each check are taken.
+
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    PackageResult, PackageStatus, PackageValidationErrorKind, ValidationOptions, Warning,
+    WarningScope,
+};
+
+/// How seriously to take the problems found by a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Ignore the problems.
+    Allow,
+    /// Report the problems as warnings, without failing.
+    Warn,
+    /// Fail because of the problems.
+    Deny,
+}
+
+impl Severity {
+    /// Parse a severity, as one of `allow`, `warn`, or `deny`.
+    #[must_use]
+    pub fn parse(severity: &str) -> Option<Self> {
+        match severity {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+        })
+    }
+}
+
+/// The severity for the problems found by the named check.
+///
+/// Checks which fail packages default to [`Severity::Deny`], and advisory checks (whose names are
+/// the [`Warning::code`]s they produce) default to [`Severity::Warn`]. With `deny_warnings`, any
+/// check at [`Severity::Warn`] is treated as [`Severity::Deny`].
+pub(crate) fn severity(
+    severities: &BTreeMap<String, Severity>,
+    deny_warnings: bool,
+    check: &str,
+    default: Severity,
+) -> Severity {
+    match severities.get(check).copied().unwrap_or(default) {
+        Severity::Warn if deny_warnings => Severity::Deny,
+        severity => severity,
+    }
+}
+
+/// Adjust the outcome of checking a package for the configured severities.
+///
+/// A failure from a check set to [`Severity::Warn`] becomes a warning, and one from a check set
+/// to [`Severity::Allow`] is dropped, so that the package passes. A warning from a check set to
+/// [`Severity::Deny`] fails the package, as [`PackageValidationErrorKind::CheckFailed`].
+pub(crate) fn apply_to_package(result: &mut PackageResult, options: &ValidationOptions) {
+    let severity =
+        |check: &str, default| severity(&options.severities, options.deny_warnings, check, default);
+    if let PackageStatus::Failed(kind) = &result.status {
+        if let Some(check) = kind.check_name() {
+            match severity(check, Severity::Deny) {
+                Severity::Deny => {}
+                Severity::Warn => {
+                    result.warnings.push(Warning {
+                        scope: WarningScope::Package(result.package.clone()),
+                        code: check.to_owned(),
+                        message: kind.to_string(),
+                    });
+                    result.status = PackageStatus::Passed;
+                }
+                Severity::Allow => result.status = PackageStatus::Passed,
+            }
+        }
+    }
+    let mut denied = None;
+    result
+        .warnings
+        .retain(|warning| match severity(&warning.code, Severity::Warn) {
+            Severity::Allow => false,
+            Severity::Warn => true,
+            Severity::Deny => {
+                denied.get_or_insert(PackageValidationErrorKind::CheckFailed {
+                    check: warning.code.clone(),
+                    reason: warning.message.clone(),
+                });
+                false
+            }
+        });
+    if let (PackageStatus::Passed, Some(kind)) = (&result.status, denied) {
+        result.status = PackageStatus::Failed(kind);
+    }
+}
+
+/// Drop the warnings about the workspace as a whole from checks set to [`Severity::Allow`], and
+/// take out those from checks set to [`Severity::Deny`], returning their messages.
+pub(crate) fn apply_to_workspace(
+    warnings: &mut Vec<Warning>,
+    options: &ValidationOptions,
+) -> Vec<String> {
+    let mut denied = Vec::new();
+    warnings.retain(|warning| {
+        match severity(
+            &options.severities,
+            options.deny_warnings,
+            &warning.code,
+            Severity::Warn,
+        ) {
+            Severity::Allow => false,
+            Severity::Warn => true,
+            Severity::Deny => {
+                denied.push(warning.message.clone());
+                false
+            }
+        }
+    });
+    denied
+}
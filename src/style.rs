@@ -0,0 +1,73 @@
+//! Coloring output with ANSI escape codes.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Whether the progress output written to stderr by this library is colored.
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Set whether the `--verbose` progress output written to stderr by this library (such as the
+/// `PASS:` and `FAIL:` lines) is colored.
+///
+/// This is off by default.
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+/// A color to write text in.
+#[derive(Debug, Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+}
+
+impl Color {
+    /// The ANSI escape code which switches to this color.
+    fn code(self) -> &'static str {
+        match self {
+            Self::Red => "\x1b[31m",
+            Self::Green => "\x1b[32m",
+            Self::Yellow => "\x1b[33m",
+        }
+    }
+}
+
+/// A value which is displayed in a color, if coloring is enabled.
+#[derive(Debug, Clone, Copy)]
+pub struct Paint<T> {
+    value: T,
+    color: Color,
+    enabled: bool,
+}
+
+/// Display `value` in `color` if `enabled` is set, or exactly as it is otherwise.
+pub fn paint<T: fmt::Display>(value: T, color: Color, enabled: bool) -> Paint<T> {
+    Paint {
+        value,
+        color,
+        enabled,
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Paint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.enabled {
+            write!(f, "{}{}\x1b[0m", self.color.code(), self.value)
+        } else {
+            self.value.fmt(f)
+        }
+    }
+}
+
+/// The prefix for progress lines about packages which pass.
+pub(crate) fn pass() -> Paint<&'static str> {
+    paint("PASS:", Color::Green, COLOR.load(Ordering::Relaxed))
+}
+
+/// The prefix for progress lines about packages which fail.
+pub(crate) fn fail() -> Paint<&'static str> {
+    paint("FAIL:", Color::Red, COLOR.load(Ordering::Relaxed))
+}
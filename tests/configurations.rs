@@ -0,0 +1,88 @@
+//! Checking a workspace under several configurations, in `tests/fixtures/optional-path-dep`.
+//!
+//! The workspace root is `workspace`, with the one member `app` (which passes). It has a path
+//! dependency on `base` and an optional one on `extra`, which both fail and live outside the
+//! workspace. Only runs with several configurations resolve dependencies, and of those only the
+//! all-features configuration surfaces `extra`.
+
+use std::{path::PathBuf, process::Command};
+
+/// The path to the root manifest of the fixture workspace.
+fn root_manifest() -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "fixtures",
+        "optional-path-dep",
+        "workspace",
+        "Cargo.toml",
+    ]
+    .iter()
+    .collect()
+}
+
+/// Run the binary on the fixture workspace with the given extra arguments, returning its exit
+/// code and stdout.
+fn run(args: &[&str]) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .arg(root_manifest())
+        .args(["--color", "never", "--stdout", "--decorate"])
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).expect("the output is UTF-8"),
+    )
+}
+
+#[test]
+fn default_features_skip_optional_path_dependency() {
+    let (code, stdout) = run(&[]);
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(
+        stdout.contains("1 package checked, 1 passing, 0 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn single_platform_checks_only_members() {
+    let (code, stdout) = run(&["--filter-platform", "x86_64-unknown-linux-gnu"]);
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(!stdout.contains("Package base"), "{stdout}");
+    assert!(
+        stdout.contains("1 package checked, 1 passing, 0 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn several_platforms_surface_path_dependency() {
+    let (code, stdout) = run(&[
+        "--filter-platform",
+        "x86_64-unknown-linux-gnu",
+        "--filter-platform",
+        "aarch64-unknown-linux-gnu",
+    ]);
+    assert_eq!(code, Some(1), "{stdout}");
+    assert!(stdout.contains("Package base"), "{stdout}");
+    assert!(!stdout.contains("Package extra"), "{stdout}");
+    assert!(
+        stdout.contains("2 packages checked, 1 passing, 1 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn all_features_surface_optional_path_dependency() {
+    let (code, stdout) = run(&["--check-all-features"]);
+    assert_eq!(code, Some(1), "{stdout}");
+    assert!(stdout.contains("Package extra"), "{stdout}");
+    assert!(stdout.contains("found with: all features"), "{stdout}");
+    assert!(
+        stdout.contains("3 packages checked, 1 passing, 2 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,3 @@
+# A hook which fails the `shared` package, printing which package it checked.
+echo "checked $CARGO_WORKSPACE_LINTS_PACKAGE_NAME"
+[ "$CARGO_WORKSPACE_LINTS_PACKAGE_NAME" != shared ]
@@ -0,0 +1,51 @@
+//! Running hooks on the fixture workspace in `tests/fixtures/out-of-tree`, with the hook in
+//! `tests/fixtures/hooks/not-shared.sh`, which fails only the `shared` package.
+
+use std::process::Command;
+
+/// Run the binary on the fixture workspace with the hook and the given extra arguments,
+/// returning its exit code and stderr.
+fn run(args: &[&str]) -> (Option<i32>, String) {
+    let hook = concat!(
+        "sh ",
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/hooks/not-shared.sh"
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/out-of-tree/workspace/Cargo.toml"
+        ))
+        .args(["--color", "never", "--hook", hook])
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    (
+        output.status.code(),
+        String::from_utf8(output.stderr).expect("the output is UTF-8"),
+    )
+}
+
+#[test]
+fn output_of_failing_hooks_is_shown() {
+    let (code, stderr) = run(&[]);
+    assert_eq!(code, Some(1), "{stderr}");
+    assert!(stderr.contains("checked shared"), "{stderr}");
+    assert!(!stderr.contains("checked member"), "{stderr}");
+    assert!(stderr.contains("Package shared"), "{stderr}");
+}
+
+#[test]
+fn output_of_every_hook_is_shown_when_verbose() {
+    let (_, stderr) = run(&["--verbose"]);
+    assert!(stderr.contains("checked shared"), "{stderr}");
+    assert!(stderr.contains("checked member"), "{stderr}");
+}
+
+#[test]
+fn hook_output_is_hidden_when_quiet() {
+    let (code, stderr) = run(&["--quiet"]);
+    assert_eq!(code, Some(1), "{stderr}");
+    assert!(!stderr.contains("checked shared"), "{stderr}");
+}
@@ -0,0 +1,38 @@
+//! Checking that the manual page from `cargo workspace-lints man` keeps up with the arguments.
+
+use std::process::Command;
+
+/// Run the binary with the given arguments, returning its stdout.
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).expect("the output is UTF-8")
+}
+
+#[test]
+fn man_page_lists_every_option_in_help() {
+    let help = run(&["--help"]);
+    let man_page = run(&["man"]);
+    let options = help
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix("--"))
+        .map(|option| option.trim_end_matches([',', '.', '`', ')']))
+        .filter(|option| !option.is_empty() && !option.contains('='));
+    for option in options {
+        let escaped = format!("\\fB\\-\\-{}\\fR", option.replace('-', "\\-"));
+        assert!(man_page.contains(&escaped), "`--{option}` is missing");
+    }
+}
+
+#[test]
+fn man_page_lists_possible_values() {
+    let man_page = run(&["man"]);
+    for format in ["human", "tree", "json", "github", "sarif", "junit", "patch"] {
+        assert!(man_page.contains(&format!("\\fB{format}\\fR")), "{format}");
+    }
+    assert!(man_page.contains("[default: set\\-true]"));
+}
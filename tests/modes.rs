@@ -0,0 +1,286 @@
+//! Running each of the binary's modes on the fixture workspace in `tests/fixtures/out-of-tree`,
+//! checking the exit code and output of each.
+//!
+//! The workspace root is `workspace`, with the members `member` (which passes), `unlinted` (which
+//! fails, having no `[lints]` table), and `../shared` (which passes). Modes which change files run
+//! on a copy of the fixture.
+
+use cargo_workspace_lints::json::{Report, Status};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The fixture directory, holding `workspace` and `shared`.
+fn fixture_dir() -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "fixtures",
+        "out-of-tree",
+    ]
+    .iter()
+    .collect()
+}
+
+/// Copy the directory `from` to `to`, with everything in it.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let to = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &to);
+        } else {
+            fs::copy(entry.path(), to).unwrap();
+        }
+    }
+}
+
+/// A copy of the fixture in a temporary directory, which is removed when this is dropped.
+struct FixtureCopy(PathBuf);
+
+impl FixtureCopy {
+    /// Copy the fixture to a directory named for the test.
+    fn new(test: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-workspace-lints-{test}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        copy_dir(&fixture_dir(), &dir);
+        Self(dir)
+    }
+
+    /// The path to `path` within the copy of the workspace root.
+    fn workspace(&self, path: &str) -> PathBuf {
+        self.0.join("workspace").join(path)
+    }
+}
+
+impl Drop for FixtureCopy {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run the binary with the given arguments, returning its exit code and stdout.
+fn run(args: &[&str], manifest: &Path) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .args(args)
+        .arg(manifest)
+        .args(["--color", "never", "--stdout"])
+        .output()
+        .expect("the binary runs");
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).expect("the output is UTF-8"),
+    )
+}
+
+/// Run the binary on the fixture itself, with the summary, returning its exit code and stdout.
+fn run_on_fixture(args: &[&str]) -> (Option<i32>, String) {
+    let args = [args, &["--decorate"]].concat();
+    run(&args, &fixture_dir().join("workspace").join("Cargo.toml"))
+}
+
+#[test]
+fn fix_edits_failing_manifests() {
+    let copy = FixtureCopy::new("fix");
+    let (code, stdout) = run(&["--fix"], &copy.workspace("Cargo.toml"));
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(stdout.contains("Fixed 1 of 1 failing packages"), "{stdout}");
+    let manifest = fs::read_to_string(copy.workspace("unlinted/Cargo.toml")).unwrap();
+    assert!(
+        manifest.ends_with("[lints]\nworkspace = true\n"),
+        "{manifest}"
+    );
+    let (code, stdout) = run(&[], &copy.workspace("Cargo.toml"));
+    assert_eq!(code, Some(0), "{stdout}");
+}
+
+#[test]
+fn fix_plan_lists_edits_without_making_them() {
+    let (code, stdout) = run_on_fixture(&["--fix-plan"]);
+    assert_eq!(code, Some(0), "{stdout}");
+    let plan: serde_json::Value = serde_json::from_str(&stdout).expect("the plan is JSON");
+    let [fix] = plan.as_array().expect("the plan is an array").as_slice() else {
+        panic!("expected one fix: {stdout}");
+    };
+    assert_eq!(fix["edit"]["kind"], "insert_workspace_key", "{stdout}");
+    assert!(fix["manifest_path"]
+        .as_str()
+        .is_some_and(|path| path.ends_with("unlinted/Cargo.toml")));
+}
+
+#[test]
+fn json_report_can_be_read_back() {
+    let (code, stdout) = run(
+        &["--format", "json"],
+        &fixture_dir().join("workspace").join("Cargo.toml"),
+    );
+    assert_eq!(code, Some(1), "{stdout}");
+    let report: Report = serde_json::from_str(&stdout).expect("the report is JSON");
+    let mut statuses = report
+        .packages
+        .iter()
+        .map(|package| (package.name.as_str(), package.status.clone()))
+        .collect::<Vec<_>>();
+    statuses.sort_by_key(|(name, _)| *name);
+    assert_eq!(
+        statuses,
+        [
+            ("member", Status::Passed),
+            ("shared", Status::Passed),
+            ("unlinted", Status::Failed)
+        ]
+    );
+    let [failure] = report.failing_packages.as_slice() else {
+        panic!("expected one failing package: {stdout}");
+    };
+    assert_eq!(failure.kind.kind, "missing");
+}
+
+#[test]
+fn sarif_report_points_at_failing_manifest() {
+    let (code, stdout) = run(
+        &["--format", "sarif"],
+        &fixture_dir().join("workspace").join("Cargo.toml"),
+    );
+    assert_eq!(code, Some(1), "{stdout}");
+    let log: serde_json::Value = serde_json::from_str(&stdout).expect("the log is JSON");
+    let results = log["runs"][0]["results"]
+        .as_array()
+        .expect("the run has results");
+    assert_eq!(results.len(), 1, "{stdout}");
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(
+        results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "unlinted/Cargo.toml"
+    );
+}
+
+#[test]
+fn baseline_skips_known_failures() {
+    let copy = FixtureCopy::new("baseline");
+    let baseline = copy.0.join("baseline.json");
+    let baseline = baseline.to_str().unwrap();
+    let (code, stdout) = run(
+        &["--write-baseline", baseline],
+        &copy.workspace("Cargo.toml"),
+    );
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(stdout.contains("Wrote 1 known failures"), "{stdout}");
+    let (code, stdout) = run(
+        &["--baseline", baseline, "--decorate"],
+        &copy.workspace("Cargo.toml"),
+    );
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(
+        stdout.contains("2 packages checked, 2 passing, 0 failing, 1 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn migrate_moves_member_lints_to_root() {
+    let copy = FixtureCopy::new("migrate");
+    let unlinted = copy.workspace("unlinted/Cargo.toml");
+    let mut manifest = fs::read_to_string(&unlinted).unwrap();
+    manifest.push_str("\n[lints.clippy]\npedantic = \"warn\"\n");
+    fs::write(&unlinted, manifest).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .args(["workspace-lints", "migrate"])
+        .arg(copy.workspace("Cargo.toml"))
+        .output()
+        .expect("the binary runs");
+    let stdout = String::from_utf8(output.stdout).expect("the output is UTF-8");
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(
+        stdout.contains("workspace.lints.clippy.pedantic = \"warn\""),
+        "{stdout}"
+    );
+    let root = fs::read_to_string(copy.workspace("Cargo.toml")).unwrap();
+    assert!(
+        root.contains("[workspace.lints.clippy]\npedantic = \"warn\"\n"),
+        "{root}"
+    );
+    let (code, stdout) = run(&[], &copy.workspace("Cargo.toml"));
+    assert_eq!(code, Some(0), "{stdout}");
+}
+
+#[test]
+fn changed_only_checks_staged_manifests() {
+    let copy = FixtureCopy::new("changed-only");
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&copy.0)
+            .status()
+            .expect("git runs");
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "-A"]);
+    git(&["commit", "-q", "-m", "Add the fixture"]);
+    let member = copy.workspace("member/Cargo.toml");
+    let mut manifest = fs::read_to_string(&member).unwrap();
+    manifest.push_str("# Changed.\n");
+    fs::write(&member, manifest).unwrap();
+    git(&["add", "-A"]);
+
+    let (code, stdout) = run(
+        &["--changed-only", "--decorate"],
+        &copy.workspace("Cargo.toml"),
+    );
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(
+        stdout.contains("1 package checked, 1 passing, 0 failing, 2 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn severity_warn_reports_failures_without_failing() {
+    let (code, stdout) = run_on_fixture(&["--severity", "lints=warn"]);
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(
+        stdout.contains("[lints] No `workspace.lints` field found"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("3 packages checked, 3 passing, 0 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn rustflags_overriding_root_lints_are_reported() {
+    let copy = FixtureCopy::new("rustflags");
+    fs::create_dir_all(copy.workspace(".cargo")).unwrap();
+    fs::write(
+        copy.workspace(".cargo/config.toml"),
+        "[build]\nrustflags = [\"-A\", \"unsafe_code\"]\n",
+    )
+    .unwrap();
+    let args = ["--check-rustflags", "--exclude", "unlinted", "--decorate"];
+
+    let (code, stdout) = run(&args, &copy.workspace("Cargo.toml"));
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(
+        stdout.contains("[rustflags-override] `build.rustflags` in `.cargo/config.toml` sets"),
+        "{stdout}"
+    );
+
+    let denied = [&args[..], &["--severity", "rustflags-override=deny"]].concat();
+    let (code, stdout) = run(&denied, &copy.workspace("Cargo.toml"));
+    assert_eq!(code, Some(1), "{stdout}");
+    assert!(
+        stdout.contains("sets `unsafe_code` to `allow`, overriding `[workspace.lints]`"),
+        "{stdout}"
+    );
+}
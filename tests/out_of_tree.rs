@@ -0,0 +1,135 @@
+//! Checking a workspace with a member outside the root directory, in
+//! `tests/fixtures/out-of-tree`.
+//!
+//! The workspace root is `workspace`, with the members `member` (which passes), `unlinted` (which
+//! fails), and `../shared` (which passes, and lives beside the root rather than under it).
+
+use std::{path::PathBuf, process::Command};
+
+/// The path to the root manifest of the fixture workspace.
+fn root_manifest() -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "fixtures",
+        "out-of-tree",
+        "workspace",
+        "Cargo.toml",
+    ]
+    .iter()
+    .collect()
+}
+
+/// Run the binary on the fixture workspace with the given extra arguments, returning its exit
+/// code and stdout.
+fn run(args: &[&str]) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .arg(root_manifest())
+        .args(["--color", "never", "--stdout", "--decorate"])
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).expect("the output is UTF-8"),
+    )
+}
+
+#[test]
+fn out_of_tree_member_is_checked() {
+    let (code, stdout) = run(&[]);
+    assert_eq!(code, Some(1), "{stdout}");
+    assert!(stdout.contains("Package unlinted"), "{stdout}");
+    assert!(!stdout.contains("Package shared"), "{stdout}");
+    assert!(
+        stdout.contains("3 packages checked, 2 passing, 1 failing, 0 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn tree_shows_out_of_tree_member_under_parent() {
+    let (_, stdout) = run(&["--format", "tree"]);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert!(lines.contains(&"├── .."), "{stdout}");
+    assert!(lines.contains(&"│   └── shared [PASS]"), "{stdout}");
+    assert!(lines.contains(&"└── unlinted [FAIL]"), "{stdout}");
+}
+
+#[test]
+fn exclude_matches_out_of_tree_member_by_relative_path() {
+    let (_, stdout) = run(&["--exclude", "../shared"]);
+    assert!(
+        stdout.contains("2 packages checked, 1 passing, 1 failing, 1 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn patch_paths_are_relative_to_root() {
+    let (_, stdout) = run(&["--format", "patch"]);
+    assert!(
+        stdout.starts_with("diff --git a/unlinted/Cargo.toml b/unlinted/Cargo.toml\n"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn patch_leaves_out_absolute_member_paths() {
+    let manifest_path = root_manifest()
+        .with_file_name("unlinted")
+        .join("Cargo.toml");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .args(["workspace-lints", "--format", "patch", "--members"])
+        .arg(&manifest_path)
+        .output()
+        .expect("the binary runs");
+    let stderr = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    assert!(output.stdout.is_empty(), "{stderr}");
+    assert!(
+        stderr.contains(&format!(
+            "left the fix for {} out of the patch",
+            manifest_path.display()
+        )),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn sarif_uri_of_relative_member_path_is_absolute() {
+    let workspace = root_manifest().with_file_name("");
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .args(["workspace-lints", "--format", "sarif", "--members"])
+        .arg("unlinted/Cargo.toml")
+        .current_dir(&workspace)
+        .output()
+        .expect("the binary runs");
+    let stdout = String::from_utf8(output.stdout).expect("the output is UTF-8");
+    let uri = format!(
+        "\"uri\": \"file://{}\"",
+        workspace.join("unlinted").join("Cargo.toml").display()
+    );
+    assert!(stdout.contains(&uri), "{stdout}");
+}
+
+#[test]
+fn plain_output_is_unchanged() {
+    let output = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .arg(root_manifest())
+        .output()
+        .expect("the binary runs");
+    let stderr = String::from_utf8(output.stderr).expect("the output is UTF-8");
+    // Exactly what was printed before the header, summary, and wrapping were added.
+    let expected = format!(
+        "Failed to validate:\n\
+         Failing packages:\n\
+         * Package unlinted 0.1.0 (path+file://{}):\n     \
+         No `workspace.lints` field found\n",
+        root_manifest().with_file_name("unlinted").display()
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr, expected);
+    assert!(output.stdout.is_empty());
+}
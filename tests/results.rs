@@ -0,0 +1,109 @@
+//! Checking the fixture workspace in `tests/fixtures/out-of-tree` through the library, asserting
+//! on the structured result for each package.
+
+use cargo_metadata::MetadataCommand;
+use cargo_workspace_lints::{
+    PackageStatus, PackageValidationErrorKind, ValidationOptions, WorkspaceCheck,
+};
+use std::sync::Arc;
+
+#[test]
+fn check_workspace_returns_result_for_each_member() {
+    let mut metadata_command = MetadataCommand::new();
+    metadata_command.no_deps().manifest_path(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/out-of-tree/workspace/Cargo.toml"
+    ));
+    let mut results = cargo_workspace_lints::check_workspace(
+        &metadata_command,
+        &ValidationOptions::default(),
+        false,
+    )
+    .expect("the fixture workspace can be checked");
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    let names = results
+        .iter()
+        .map(|result| result.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["member", "shared", "unlinted"]);
+    assert!(matches!(results[0].status, PackageStatus::Passed));
+    assert!(matches!(results[1].status, PackageStatus::Passed));
+    assert!(results[1]
+        .manifest_path
+        .ends_with("out-of-tree/shared/Cargo.toml"));
+    assert!(matches!(
+        results[2].status,
+        PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsMissing)
+    ));
+}
+
+/// Fails every package, naming the package from the manifest it was given.
+#[derive(Debug)]
+struct NameFromManifest;
+
+impl WorkspaceCheck for NameFromManifest {
+    fn name(&self) -> &str {
+        "name-from-manifest"
+    }
+
+    fn check(
+        &self,
+        _package: &cargo_metadata::Package,
+        manifest: &toml::Table,
+    ) -> Result<(), PackageValidationErrorKind> {
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(toml::Value::as_str)
+            .unwrap_or("<missing>");
+        Err(PackageValidationErrorKind::CheckFailed {
+            check: self.name().to_owned(),
+            reason: name.to_owned(),
+        })
+    }
+}
+
+#[test]
+fn custom_checks_run_after_built_in_check_with_parsed_manifest() {
+    let mut metadata_command = MetadataCommand::new();
+    metadata_command.no_deps().manifest_path(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/out-of-tree/workspace/Cargo.toml"
+    ));
+    let options = ValidationOptions {
+        checks: vec![Arc::new(NameFromManifest)],
+        ..ValidationOptions::default()
+    };
+    let mut results = cargo_workspace_lints::check_workspace(&metadata_command, &options, false)
+        .expect("the fixture workspace can be checked");
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    let statuses = results
+        .iter()
+        .map(|result| match &result.status {
+            PackageStatus::Failed(PackageValidationErrorKind::CheckFailed { reason, .. }) => {
+                reason.as_str()
+            }
+            PackageStatus::Failed(PackageValidationErrorKind::WorkspaceLintsMissing) => "missing",
+            _ => "other",
+        })
+        .collect::<Vec<_>>();
+    // `unlinted` fails the built-in check, so the custom one isn't run on it.
+    assert_eq!(statuses, ["member", "shared", "missing"]);
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_validate_workspace_keeps_its_signature() {
+    let mut metadata_command = MetadataCommand::new();
+    metadata_command.no_deps().manifest_path(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/out-of-tree/workspace/Cargo.toml"
+    ));
+    let error = cargo_workspace_lints::validate_workspace(&metadata_command, false)
+        .expect_err("`unlinted` fails");
+    assert!(matches!(
+        error,
+        cargo_workspace_lints::WorkspaceValidationError::FailingPackages(failures)
+            if failures.len() == 1
+    ));
+}
@@ -0,0 +1,138 @@
+//! Watching a copy of the fixture workspace in `tests/fixtures/out-of-tree` for changes.
+//!
+//! The workspace root is `workspace`, with the members `member` (which passes), `unlinted` (which
+//! fails until the test gives it lints), and `../shared` (which passes).
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+/// How long to wait for the binary to finish a check before giving up.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Copy the directory `from` to `to`, with everything in it.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let to = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &to);
+        } else {
+            fs::copy(entry.path(), to).unwrap();
+        }
+    }
+}
+
+/// A temporary directory, which is removed when this is dropped.
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Kills the binary if the test fails before it does.
+struct Watcher(Child);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Start watching the workspace copied to `dir`, returning the running binary and the lines it
+/// writes to stderr, where it says when it's waiting for changes.
+fn watch(dir: &Path) -> (Watcher, Receiver<String>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-workspace-lints"))
+        .arg("workspace-lints")
+        .arg(dir.join("workspace").join("Cargo.toml"))
+        .args(["--watch", "--color", "never", "--stdout", "--decorate"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the binary runs");
+    let stderr = child.stderr.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    (Watcher(child), receiver)
+}
+
+/// Wait for the binary to finish a check and start waiting for changes.
+fn wait_for_check(stderr: &Receiver<String>) {
+    loop {
+        let line = stderr
+            .recv_timeout(CHECK_TIMEOUT)
+            .expect("the check finishes");
+        if line.starts_with("Watching ") {
+            return;
+        }
+    }
+}
+
+#[test]
+fn manifest_changes_are_checked_but_source_changes_are_not() {
+    let temp = TempDir(std::env::temp_dir().join(format!(
+        "cargo-workspace-lints-watch-{}",
+        std::process::id()
+    )));
+    let dir = &temp.0;
+    let _ = fs::remove_dir_all(dir);
+    copy_dir(
+        &[
+            env!("CARGO_MANIFEST_DIR"),
+            "tests",
+            "fixtures",
+            "out-of-tree",
+        ]
+        .iter()
+        .collect::<PathBuf>(),
+        dir,
+    );
+    let (mut watcher, stderr) = watch(dir);
+    wait_for_check(&stderr);
+
+    let unlinted = dir.join("workspace").join("unlinted");
+    let mut manifest = fs::read_to_string(unlinted.join("Cargo.toml")).unwrap();
+    manifest.push_str("\n[lints]\nworkspace = true\n");
+    fs::write(unlinted.join("Cargo.toml"), manifest).unwrap();
+    wait_for_check(&stderr);
+
+    // Only manifests and config files are polled, so this doesn't start another check.
+    fs::write(unlinted.join("src").join("lib.rs"), "//! Changed.\n").unwrap();
+    assert!(stderr.recv_timeout(Duration::from_secs(3)).is_err());
+
+    watcher.0.kill().unwrap();
+    let mut stdout = String::new();
+    watcher
+        .0
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut stdout)
+        .unwrap();
+    drop(watcher);
+    assert!(
+        stdout.contains("3 packages checked, 2 passing, 1 failing, 0 skipped"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("PASS: Package unlinted"), "{stdout}");
+    assert!(
+        stdout.ends_with("3 packages checked, 3 passing, 0 failing, 0 skipped\n"),
+        "{stdout}"
+    );
+}